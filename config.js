@@ -97,7 +97,9 @@ function genUiConfig(args) {
 
     // Order Bar
     const ordIng = (ing, chance, price) => ({ing, chance, price});
-    const orderCfg = (weight, depreciation_seconds, ings) => ({weight, depreciation_seconds, ings});
+    // reputation_weight is added to weight per point of reputation, so fancier orders get more common.
+    // strict_order orders must be assembled bottom to top, at difficulties with strict_assembly.
+    const orderCfg = (weight, reputation_weight, depreciation_seconds, patience_seconds, leave_penalty, strict_order, ings) => ({weight, reputation_weight, depreciation_seconds, patience_seconds, leave_penalty, strict_order, ings});
     const order_bar = {
         pos: pos(1200, 400),
         order_margin: 20,
@@ -108,27 +110,32 @@ function genUiConfig(args) {
             center_and_fit: true, is_command: true}),
         text_remaining: textCfg(10, -270, 48, {
             style: "white"}),
-        progress_bar: progressCfg(0, 30, 100, 5),
+        text_rejected: textCfg(0, 40, 48, {
+            center_and_fit: true, style: "red", alpha: 1}),
+        reject_message: "Bottom first!",
+        reject_seconds: 1.5, // how long text_rejected shows after an out-of-order delivery
+        progress_bar: progressCfg(0, 30, 100, 5), // customer patience
+        leave_offset: pos(0, 300),                // orders drop out of the bar when the customer leaves
         money_sound: playbackCfg("Coins"),
         orders: [
-            orderCfg(1, 5, [ // Burger
+            orderCfg(1, 0, 5, 40, 3, true, [ // Burger
                 ordIng("BurgerBottom", 1, 3),
                 ordIng("CookedPatty", 1, 8),
                 ordIng("LettuceLeaf", .7, 4),
                 ordIng("TomatoSlice", .6, 5),
                 ordIng("BurgerTop", 1, 3)]),
-            orderCfg(.5, 5, [ // Salad
+            orderCfg(.5, -.05, 5, 30, 2, false, [ // Salad
                 ordIng("LettuceLeaf", 1, 8),
                 ordIng("TomatoSlice", 1, 10)]),
-            orderCfg(.5, 8, [ // Curry Crab
+            orderCfg(.5, .15, 8, 60, 5, false, [ // Curry Crab
                 ordIng("CurryCrab", 1, 30),
                 ordIng("Dumplings", 1, 10)]),
-            orderCfg(1, 8, [ // Egg Sandwich
+            orderCfg(1, 0, 8, 45, 3, true, [ // Egg Sandwich
                 ordIng("BurgerBottom", 1, 5),
                 ordIng("EggsFried", 1, 7),
                 ordIng("BaconCooked", .3, 8),
                 ordIng("BurgerTop", 1, 5) ]),
-            orderCfg(1, 8, [ // Bacon Sandwich
+            orderCfg(1, .05, 8, 45, 3, true, [ // Bacon Sandwich
                 ordIng("BurgerBottom", 1, 5),
                 ordIng("BaconCooked", 1, 8),
                 ordIng("LettuceLeaf", .8, 3),
@@ -218,6 +225,8 @@ function genUiConfig(args) {
             border_alpha: .3, border_width: 5, bg_alpha: .8 }),
         text: textCfg(0,0, 48, {
             style: "black", alpha: 1 }),
+        typo_text: textCfg(0,0, 48, {
+            style: "red", alpha: 1 }),
     };
     
     // State
@@ -232,6 +241,19 @@ function genUiConfig(args) {
         progress: progressCfg(0, 0, 200, 5),
     };
 
+    // Day summary, shown between closing and the store
+    const day_summary = {
+        pos: pos(1300, 250),
+        bg: bgCfg(-50, -70, 1000, 620, "black", "orange", {
+            border_alpha: .3 }),
+        title: textCfg(0, 0, 64, {
+            style: "black", alpha: 1 }),
+        text: textCfg(0, 0, 32, {
+            style: "black", alpha: .8 }),
+        width: 900,
+        line_height: 40,
+    };
+
     // Money
     const money = {
         pos: pos(50, 50),
@@ -240,12 +262,31 @@ function genUiConfig(args) {
         text: textCfg(40, 40, 128, {
             style: "black", filled_style: "gold", stroke: true, alpha: 1 }),
     };
+
+    // Reputation, next to the money
+    const reputation = {
+        pos: pos(470, 50),
+        bg: bgCfg(0, -20, 300, 250, "black", "green", {
+            border_alpha: .3 }),
+        text: textCfg(30, 60, 96, {
+            style: "black", filled_style: "gold", stroke: true, alpha: 1 }),
+    };
+
+    // Serve streak, shown over the order bar while it's going
+    const streak = {
+        pos: pos(1900, 130),
+        text: textCfg(0, 0, 64, {
+            style: "gold", stroke: true, alpha: 1 }),
+        min_streak: 2,    // shorter streaks aren't shown
+        pulse_scale: 1.5, // the text pops to this size when the streak grows...
+        pulse_speed: 1,   // ... and shrinks back this much per second
+    };
     
     // FPS
     const fps = textCfg(0, 0, 30, {
             style: "black", alpha: .7 });
 
-    return {images, sounds, order_bar, ingredient_area, preparation_area, store, keyword_entry, state, money, fps};
+    return {images, sounds, order_bar, ingredient_area, preparation_area, store, keyword_entry, state, day_summary, money, reputation, streak, fps};
 }
 
 function genGameConfig(args) {
@@ -257,6 +298,8 @@ function genGameConfig(args) {
     // Order Bar
     const order_bar = {
         order_period: 6,
+        max_visible_orders: 5, // more customers than this wait in line...
+        max_waiting: 4,        // ... up to this many, and the rest go elsewhere
     };
     
     // State
@@ -271,11 +314,159 @@ function genGameConfig(args) {
         starting_money: 0,
         max_money: 100,
     };
+
+    // Orders served in a row without typos or customers leaving pay more.  Indexed by the
+    // streak before the serve; the last multiplier is used for longer streaks.
+    const streak = {
+        multipliers: [1, 1, 1.1, 1.2, 1.3, 1.5, 1.75, 2],
+    };
+
+    // Reputation rises with quick serves and falls when customers leave unserved
+    const reputation = {
+        starting: 2.5,
+        max: 5,
+        fast_serve_seconds: 20,       // serves quicker than this count as fast
+        fast_serve_gain: .1,
+        slow_serve_gain: .03,
+        expired_loss: .3,             // a customer ran out of patience
+        missed_loss: .1,              // an order was still waiting at the end of the day
+        low_order_period_scale: 1.5,  // order_period is multiplied by this at no reputation...
+        high_order_period_scale: .6,  // ... and by this at max reputation
+        max_tip: .3,                  // fraction of an order's price tipped at max reputation
+    };
+
+    // Keyword entry
+    const keyword_entry = {
+        typo_penalty: "None", // or {Money: amount}, or {Lockout: seconds}
+    };
+
+    // How hard words are to type.  Levels below pick words by these scores.
+    const word_score = {
+        rank_weight: 1,           // per 'rank_scale' more common words
+        rank_scale: 20000,
+        length_weight: 1,         // per letter beyond 'base_length'
+        base_length: 4,
+        rare_letters: "jqxz",
+        rare_letter_weight: 0.5,
+        awkward_bigrams: ["ce", "ec", "br", "rb", "mu", "um", "nu", "un"],
+        awkward_bigram_weight: 0.25,
+        same_finger_weight: 0.25, // different letters typed with the same finger
+        row_jump_weight: 0.25,    // jumps between the top and bottom rows
+        shifted_weight: 0.25,     // capitals and symbols like "<"
+    };
+
+    // A range of word scores, and how often new words come from it
+    const wordLevel = (min_score, max_score, weight, letter_pairs = false, phrases = []) => ({min_score, max_score, weight, letter_pairs, phrases});
+
+    // Phrase keywords, for the hardest difficulty
+    const phrases = [
+        "extra crispy bacon", "hold the pickles", "no onions please", "light on the mayo",
+        "double cheese", "well done", "to go", "side of fries", "make it a combo", "extra lettuce",
+    ];
+
+    // Every area draws from the code word list
+    const codePool = ["code"];
+
+    // Per-difficulty settings, indexed by 'difficulty'
+    // keyword_allocation: "Distinguishable" keeps keywords on screen from sharing prefixes, "Any" doesn't
+    // alphabet, word_pools: replace the game's settings below for this difficulty, if not null
+    // strict_assembly: orders with strict_order must be assembled bottom to top
+    const difficulties = [
+        { // easy: 2-letter combinations
+            auto_submit: true,
+            keyword_allocation: "Distinguishable",
+            word_levels: [wordLevel(0, 1000, 1, true)],
+            alphabet: null,
+            word_pools: null,
+            strict_assembly: false,
+        },
+        { // normal: common short words, with the odd harder one
+            auto_submit: false,
+            keyword_allocation: "Distinguishable",
+            word_levels: [wordLevel(0, 1, 3), wordLevel(1, 2, 1)],
+            alphabet: null,
+            word_pools: null,
+            strict_assembly: false,
+        },
+        { // hard
+            auto_submit: false,
+            keyword_allocation: "Any",
+            word_levels: [wordLevel(1, 2, 2), wordLevel(2, 4, 1), wordLevel(0, 1000, 0.5, false, phrases)],
+            alphabet: null,
+            word_pools: null,
+            strict_assembly: true, // burgers and sandwiches are built bottom bun first
+        },
+        { // programmer: identifiers and symbol-heavy code tokens
+            auto_submit: false,
+            keyword_allocation: "Distinguishable",
+            word_levels: [wordLevel(0, 2, 2), wordLevel(2, 1000, 1)],
+            alphabet: {
+                letters: "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_&*<>()[]{}:;,.!?=+-/|'#@$%^~\"`",
+                fold_diacritics: false,
+                case_sensitive: true,
+            },
+            word_pools: {orders: codePool, ingredients: codePool, cookers: codePool, store: codePool, state: codePool},
+            strict_assembly: false,
+        },
+    ];
+
+    // Favor words with the letters and bigrams the player is weakest at
+    const adaptive_words = {
+        strength: 2,     // 0 turns this off
+        min_presses: 10, // typings needed before a letter or bigram can count as weak
+    };
+
+    // Letters words are made of, e.g. add "àâçéèêëîïôûùüÿœæ" for French word lists
+    const alphabet = {
+        letters: "abcdefghijklmnopqrstuvwxyz",
+        fold_diacritics: true, // typing "e" matches "é"
+        case_sensitive: false, // typing "a" also matches "A"
+    };
+
+    // Lists keywords are read from.  Formats are "Plain" (a word per line), "Frequency" ("word
+    // count" lines, most frequent first) and "Json" (an array of {word, tags} objects).  A list's
+    // tags are added to each of its words.
+    const word_sources = [
+        {
+            name: "general",
+            url: "https://raw.githubusercontent.com/hermitdave/FrequencyWords/refs/heads/master/content/2018/en/en_50k.txt",
+            format: "Frequency",
+            tags: ["general"],
+        },
+        {
+            name: "food",
+            url: "words/food.json",
+            format: "Json",
+            tags: [],
+        },
+        {
+            name: "code",
+            url: "words/code.txt",
+            format: "Plain",
+            tags: ["code"],
+        },
+    ];
+
+    // Tags of the words each area's keywords come from.  An empty list means any word.
+    const word_pools = {
+        orders: ["general"],
+        ingredients: ["food"],
+        cookers: [],
+        store: [],
+        state: [],
+    };
+
+    // Simulation timing
+    const timestep = {
+        tick_seconds: 1 / 60,
+        max_ticks_per_frame: 10, // frames slower than this many ticks lose the extra time
+    };
     
     return {
-        word_level: 0,
+        difficulty: 0,
         unlock_all: false,
-        ingredient_area, order_bar, state, money
+        ingredient_area, order_bar, state, money, reputation, streak, keyword_entry, timestep, difficulties, word_score, adaptive_words, alphabet,
+        word_sources, word_pools
     };
 }
 
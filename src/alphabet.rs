@@ -0,0 +1,140 @@
+use serde::{Serialize,Deserialize};
+
+/// The letters keywords are made of, and how typed text is matched against them
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AlphabetGameConfig {
+    pub letters: String,       // characters a word may contain, lowercase unless 'case_sensitive'
+    pub fold_diacritics: bool, // typing a letter without its diacritic still matches, e.g. "e" for "é"
+    pub case_sensitive: bool,  // typing "a" doesn't match "A", e.g. for code like "camelCase"
+}
+
+// Return the specified 'c' without any diacritic, for the Latin and Cyrillic letters where
+// players commonly leave them off
+fn fold_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => 's',
+        'ţ' | 'ť' | 'ŧ' | 'ț' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        'ё' => 'е',
+        _ => c,
+    }
+}
+
+impl AlphabetGameConfig {
+    /// Return whether the specified 'word' only uses our letters.  Single spaces between the
+    /// parts of a multi-word keyword, like "&mut self", are allowed too.
+    pub fn allows(&self, word: &str) -> bool {
+        word.split(' ').all(|part| !part.is_empty() && part.chars().all(|c| self.letters.contains(c)))
+    }
+
+    /// Return the form of the specified 'c' used for matching.  Each character normalizes to
+    /// exactly one character, so positions in normalized text line up with the original.
+    pub fn normalize_char(&self, c: char) -> char {
+        let c = if self.case_sensitive { c } else { c.to_lowercase().next().unwrap_or(c) };
+        if self.fold_diacritics { fold_diacritic(c) } else { c }
+    }
+
+    /// Return the form of the specified 'text' used for matching
+    pub fn normalize(&self, text: &str) -> String {
+        text.chars().map(|c| self.normalize_char(c)).collect()
+    }
+
+    /// Return whether the specified 'typed' text is the start of the specified 'keyword'
+    pub fn is_prefix(&self, typed: &str, keyword: &str) -> bool {
+        let mut keyword_chars = keyword.chars();
+        typed.chars().all(|t| keyword_chars.next().is_some_and(|k| self.normalize_char(t) == self.normalize_char(k)))
+    }
+
+    /// Return whether the specified 'typed' text is the whole of the specified 'keyword'
+    pub fn matches(&self, typed: &str, keyword: &str) -> bool {
+        typed.chars().count() == keyword.chars().count() && self.is_prefix(typed, keyword)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alphabet(letters: &str, fold_diacritics: bool, case_sensitive: bool) -> AlphabetGameConfig {
+        AlphabetGameConfig {
+            letters: letters.to_string(),
+            fold_diacritics: fold_diacritics,
+            case_sensitive: case_sensitive,
+        }
+    }
+
+    #[test]
+    fn allows() {
+        let a = alphabet("abcéf&", false, false);
+        assert!(a.allows("café"));
+        assert!(a.allows("ab &cab"));
+        assert!(!a.allows("abd"));
+        assert!(!a.allows("ab  cab"));
+        assert!(!a.allows(" ab"));
+        assert!(!a.allows(""));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let a = alphabet("abc", false, false);
+        assert!(a.matches("ABC", "abc"));
+        assert!(a.matches("abc", "AbC"));
+        assert!(a.is_prefix("Ab", "abc"));
+    }
+
+    #[test]
+    fn case_sensitive() {
+        let a = alphabet("abcABC", false, true);
+        assert!(!a.matches("abc", "aBc"));
+        assert!(a.matches("aBc", "aBc"));
+        assert!(!a.is_prefix("A", "abc"));
+    }
+
+    #[test]
+    fn diacritics() {
+        let folded = alphabet("abcdefé", true, false);
+        assert!(folded.matches("cafe", "café"));
+        assert!(folded.matches("CAFÉ", "café"));
+        assert!(folded.is_prefix("caf", "café"));
+        assert_eq!(folded.normalize("Éé"), "ee");
+
+        let exact = alphabet("abcdefé", false, false);
+        assert!(!exact.matches("cafe", "café"));
+        assert!(exact.matches("CAFÉ", "café"));
+    }
+
+    #[test]
+    fn cyrillic() {
+        let a = alphabet("её", true, false);
+        assert!(a.matches("ЕЛКА", "ёлка"));
+        assert!(!alphabet("её", false, false).matches("елка", "ёлка"));
+    }
+
+    #[test]
+    fn matches_whole_keyword() {
+        let a = alphabet("abcé", true, false);
+        assert!(!a.matches("ab", "abc"));
+        assert!(!a.matches("abcc", "abc"));
+        assert!(!a.is_prefix("abcc", "abc"));
+        assert!(a.is_prefix("", "abc"));
+
+        // Lengths are compared in characters, not bytes
+        assert!(a.matches("abce", "abcé"));
+    }
+}
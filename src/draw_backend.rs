@@ -0,0 +1,252 @@
+use crate::traits::{DrawBackend, Image, TextDims};
+
+use engine_p::images::{Images, ImagesConfig};
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, OffscreenCanvas, OffscreenCanvasRenderingContext2d};
+
+use std::collections::HashMap;
+
+///////// CanvasBackend
+
+// Draws to an offscreen canvas, which gets copied to the on-screen canvas when presenting
+pub struct CanvasBackend {
+    screen_canvas: HtmlCanvasElement,
+    screen_context: CanvasRenderingContext2d,
+    offscreen_canvas: OffscreenCanvas,
+    context: OffscreenCanvasRenderingContext2d,
+    images: Images<Image>,
+}
+
+impl CanvasBackend {
+    // Return an error if the browser can't provide a 2d context for either canvas
+    pub fn new(screen_canvas: HtmlCanvasElement, images: Images<Image>) -> Result<Self, JsError> {
+        let screen_context = screen_canvas.get_context("2d").ok().flatten()
+            .and_then(|ctx| ctx.dyn_into::<CanvasRenderingContext2d>().ok())
+            .ok_or_else(|| JsError::new("Failed getting a 2d context for the canvas"))?;
+
+        let offscreen_canvas = OffscreenCanvas::new(2560, 1440)
+            .map_err(|_| JsError::new("Failed creating an offscreen canvas"))?;
+        let context = offscreen_canvas.get_context("2d").ok().flatten()
+            .and_then(|ctx| ctx.dyn_into::<OffscreenCanvasRenderingContext2d>().ok())
+            .ok_or_else(|| JsError::new("Failed getting a 2d context for the offscreen canvas"))?;
+
+        Ok(CanvasBackend {
+            screen_canvas: screen_canvas,
+            screen_context: screen_context,
+            offscreen_canvas: offscreen_canvas,
+            context: context,
+            images: images,
+        })
+    }
+}
+
+impl DrawBackend for CanvasBackend {
+    fn set_global_alpha(&self, alpha: f64) {
+        self.context.set_global_alpha(alpha);
+    }
+
+    fn set_fill_style(&self, style: &str) {
+        self.context.set_fill_style_str(style);
+    }
+
+    fn set_stroke_style(&self, style: &str) {
+        self.context.set_stroke_style_str(style);
+    }
+
+    fn set_line_width(&self, width: f64) {
+        self.context.set_line_width(width);
+    }
+
+    fn set_font(&self, font: &str) {
+        self.context.set_font(font);
+    }
+
+    fn set_text_baseline(&self, baseline: &str) {
+        self.context.set_text_baseline(baseline);
+    }
+
+    fn begin_path(&self) {
+        self.context.begin_path();
+    }
+
+    fn close_path(&self) {
+        self.context.close_path();
+    }
+
+    fn rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.context.rect(x, y, width, height);
+    }
+
+    fn round_rect(&self, x: f64, y: f64, width: f64, height: f64, radius: f64) {
+        self.context.round_rect_with_f64(x, y, width, height, radius).expect("round rect");
+    }
+
+    fn arc(&self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool) {
+        self.context.arc_with_anticlockwise(x, y, radius, start, end, anticlockwise).expect("arc");
+    }
+
+    fn fill(&self) {
+        self.context.fill();
+    }
+
+    fn stroke(&self) {
+        self.context.stroke();
+    }
+
+    fn clip(&self) {
+        self.context.clip();
+    }
+
+    fn save(&self) {
+        self.context.save();
+    }
+
+    fn restore(&self) {
+        self.context.restore();
+    }
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.context.fill_rect(x, y, width, height);
+    }
+
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.context.clear_rect(x, y, width, height);
+    }
+
+    fn fill_text(&self, text: &str, x: f64, y: f64) {
+        self.context.fill_text(text, x, y).expect("text");
+    }
+
+    fn stroke_text(&self, text: &str, x: f64, y: f64) {
+        self.context.stroke_text(text, x, y).expect("text");
+    }
+
+    fn measure_text(&self, text: &str) -> TextDims {
+        let metrics = self.context.measure_text(text).expect("measure text");
+        TextDims {
+            width: metrics.width(),
+            ascent: metrics.actual_bounding_box_ascent(),
+            descent: metrics.actual_bounding_box_descent(),
+        }
+    }
+
+    fn draw_image(&self, image: &Image, x: f64, y: f64, gray: bool) {
+        if gray {
+            self.images.draw_gray_image(&self.context, image, x, y);
+        }
+        else {
+            self.images.draw_image(&self.context, image, x, y);
+        }
+    }
+
+    fn image_width(&self, image: &Image) -> f64 {
+        self.images.image_width(image)
+    }
+
+    fn image_height(&self, image: &Image) -> f64 {
+        self.images.image_height(image)
+    }
+
+    fn update_images_config(&mut self, cfg: &ImagesConfig<Image>) {
+        self.images.update_config(cfg);
+    }
+
+    fn present(&self) {
+        self.screen_context.clear_rect(0.0, 0.0, self.screen_canvas.width() as f64, self.screen_canvas.height() as f64);
+
+        // This only fails while the canvas has no size, and then there's nothing to show anyway
+        let _ = self.screen_context.draw_image_with_offscreen_canvas_and_dw_and_dh(
+            &self.offscreen_canvas,
+            0.0, 0.0,
+            self.screen_canvas.width() as f64, self.screen_canvas.height() as f64);
+    }
+}
+
+///////// HeadlessBackend
+
+// Draws nothing.  Only tracks image sizes, since the game's layout depends on them.
+pub struct HeadlessBackend {
+    image_sizes: HashMap<Image, (f64, f64)>,
+}
+
+impl HeadlessBackend {
+    pub fn new(cfg: &ImagesConfig<Image>) -> Self {
+        let mut ret = HeadlessBackend {
+            image_sizes: HashMap::new(),
+        };
+
+        ret.update_images_config(cfg);
+
+        ret
+    }
+}
+
+impl DrawBackend for HeadlessBackend {
+    fn set_global_alpha(&self, _alpha: f64) {}
+
+    fn set_fill_style(&self, _style: &str) {}
+
+    fn set_stroke_style(&self, _style: &str) {}
+
+    fn set_line_width(&self, _width: f64) {}
+
+    fn set_font(&self, _font: &str) {}
+
+    fn set_text_baseline(&self, _baseline: &str) {}
+
+    fn begin_path(&self) {}
+
+    fn close_path(&self) {}
+
+    fn rect(&self, _x: f64, _y: f64, _width: f64, _height: f64) {}
+
+    fn round_rect(&self, _x: f64, _y: f64, _width: f64, _height: f64, _radius: f64) {}
+
+    fn arc(&self, _x: f64, _y: f64, _radius: f64, _start: f64, _end: f64, _anticlockwise: bool) {}
+
+    fn fill(&self) {}
+
+    fn stroke(&self) {}
+
+    fn clip(&self) {}
+
+    fn save(&self) {}
+
+    fn restore(&self) {}
+
+    fn fill_rect(&self, _x: f64, _y: f64, _width: f64, _height: f64) {}
+
+    fn clear_rect(&self, _x: f64, _y: f64, _width: f64, _height: f64) {}
+
+    fn fill_text(&self, _text: &str, _x: f64, _y: f64) {}
+
+    fn stroke_text(&self, _text: &str, _x: f64, _y: f64) {}
+
+    fn measure_text(&self, _text: &str) -> TextDims {
+        // Text takes no space, so 'center_and_fit' text never needs shrinking
+        TextDims {
+            width: 0.0,
+            ascent: 0.0,
+            descent: 0.0,
+        }
+    }
+
+    fn draw_image(&self, _image: &Image, _x: f64, _y: f64, _gray: bool) {}
+
+    fn image_width(&self, image: &Image) -> f64 {
+        self.image_sizes.get(image).map_or(0.0, |size| size.0)
+    }
+
+    fn image_height(&self, image: &Image) -> f64 {
+        self.image_sizes.get(image).map_or(0.0, |size| size.1)
+    }
+
+    fn update_images_config(&mut self, cfg: &ImagesConfig<Image>) {
+        self.image_sizes = cfg.images
+            .iter()
+            .map(|img| (img.image, (img.width * cfg.scale, img.height * cfg.scale)))
+            .collect();
+    }
+
+    fn present(&self) {}
+}
@@ -0,0 +1,22 @@
+use crate::store::StoreUpgradeAction;
+use crate::traits::Image;
+
+use serde::Serialize;
+
+/// Something that happened in the game.  Areas emit these instead of changing money or playing
+/// sounds themselves, and 'GameState' dispatches them to whatever needs to react.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    OrderCreated { keyword: String },
+    OrderServed { price: i32, tip: i32, streak_bonus: i32, serve_time: f64 }, // 'serve_time' is seconds since the order was created
+    OrderMissed,                                  // the day ended before the order was served
+    OrderExpired { penalty: i32, wait_time: f64 }, // the customer ran out of patience and left
+    DeliveryRejected,                             // ingredients were delivered to a strict order out of order
+    CookingStarted { cooker_idx: usize, cooker: Image, cook_time: f64 }, // 'cooker_idx' in the config's cookers
+    CookingDone { cooker_idx: usize, cooker: Image },
+    UpgradePurchased { img: Image, action: StoreUpgradeAction, cost: i32 },
+    DayStarted,
+    DayEnded,
+    MoneyDrained { amount: i32 }, // negative, as 'money_down_amt' is
+    Typo,                         // the word being typed stopped matching any keyword
+}
@@ -2,18 +2,13 @@
 use crate::ingredients::{MovableIngredient, IngredientStack};
 use crate::painter::{BackgroundConfig, TextConfig};
 use crate::traits::{BaseGame, Image};
+use crate::word_source::WordPool;
 
 use engine_p::interpolable::{Interpolable, Pos2d};
 use serde::{Serialize,Deserialize};
-use wasm_bindgen::prelude::*;
 
 use std::collections::HashSet;
-
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
+use std::rc::Rc;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct IngredientAreaUiConfig {
@@ -69,7 +64,7 @@ impl IngredientArea {
             self.ingredients.iter_mut()
                 .filter(|ing| match &ing.text { Some(text) => **text == *keyword, None => false})
                 .for_each(|ing| {
-                    ing.text = Some(game.word_bank().get_new_word());
+                    ing.text = Some(game.word_bank().get_new_word(WordPool::Ingredients, game.rng()));
                     selected_ings.push(ing.ingredients[0].deep_clone());
                 });
         }
@@ -89,11 +84,18 @@ impl IngredientArea {
             
             let mut new_stack = IngredientStack::new(Interpolable::new_b(stack_pos, 1000.0, &self.pos));
             new_stack.add_ingredient(MovableIngredient::new(*cfg_ing, Interpolable::new((0,0).into(), 1000.0)), true, game);
-            new_stack.text = Some(game.word_bank().get_new_word());
+            new_stack.text = Some(game.word_bank().get_new_word(WordPool::Ingredients, game.rng()));
             self.ingredients.push(new_stack);
         }
     }
 
+    /// Append the keywords currently shown in the area to the specified 'keywords'
+    pub fn collect_keywords(&self, keywords: &mut Vec<Rc<String>>) {
+        for stack in self.ingredients.iter() {
+            keywords.extend(stack.text.iter().cloned());
+        }
+    }
+
     pub fn load_ingredients(&self, ings: &mut HashSet<Image>) {
         for stack in self.ingredients.iter() {
             ings.insert(stack.ingredients[0].image);
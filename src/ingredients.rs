@@ -3,16 +3,9 @@ use crate::painter::{ProgressBarConfig, TextConfig};
 use crate::traits::{BaseGame, Image};
 
 use engine_p::interpolable::{Interpolable, Pos2d};
-use wasm_bindgen::prelude::*;
 
 use std::rc::Rc;
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
-
 struct MovableIngredientThinkResult {
     pos_done: bool,
     incoming_ing_done: bool,
@@ -170,9 +163,9 @@ impl IngredientStack {
 
         // Draw the overlay in the bottom-right corner of the first ingredient
         for (ing, overlay) in self.ingredients.iter().zip(self.overlay.iter()) {
-            let images = game.painter().images();
-            let x_off = images.image_width(&ing.image) - (images.image_width(overlay)/2.0);
-            let y_off = images.image_height(&ing.image) - (images.image_height(overlay)/2.0);
+            let painter = game.painter();
+            let x_off = painter.image_width(&ing.image) - (painter.image_width(overlay)/2.0);
+            let y_off = painter.image_height(&ing.image) - (painter.image_height(overlay)/2.0);
             let overlay_pos = ing.pos.cur() + (x_off, y_off).into();
 
             game.painter().draw_image(overlay, &overlay_pos);
@@ -191,14 +184,14 @@ impl IngredientStack {
 
     pub fn add_ingredient(&mut self, mut ingredient: MovableIngredient, immediate: bool, game: &dyn BaseGame) {
 
-        let cur_height: f64 = self.ingredients.iter().map(|ing| game.painter().images().image_height(&ing.image)).sum();
+        let cur_height: f64 = self.ingredients.iter().map(|ing| game.painter().image_height(&ing.image)).sum();
 
         // Account for padding between ingredients
         //cur_height += 10.0 *self.ingredients.len() as f64;
 
         let end = Pos2d::new(
             0.0,
-            -cur_height - game.painter().images().image_height(&ingredient.image)
+            -cur_height - game.painter().image_height(&ingredient.image)
         );
 
         ingredient.pos.rebase(Some(self.pos.clone()), end, immediate);
@@ -220,12 +213,32 @@ impl IngredientStack {
         }
     }
 
+    /// Like 'try_ungray_ingredients', but only fill slots from the bottom up, stopping at the first
+    /// one that none of 'ings' can fill.  Return whether any of 'ings' were left over that could
+    /// have filled a slot further up, meaning they were delivered out of order.
+    pub fn try_ungray_ingredients_in_order(&mut self, ings: &mut Vec<MovableIngredient>) -> bool {
+        for my_ing in self.ingredients.iter_mut() {
+            if !my_ing.grayed_out || my_ing.incoming_ing.is_some() {
+                continue;
+            }
+
+            match ings.iter().position(|ing| ing.image == my_ing.image) {
+                Some(i) => my_ing.set_incoming_ing(ings.remove(i)),
+                None => break,
+            }
+        }
+
+        self.ingredients.iter()
+            .filter(|my_ing| my_ing.grayed_out && my_ing.incoming_ing.is_none())
+            .any(|my_ing| ings.iter().any(|ing| ing.image == my_ing.image))
+    }
+
     // Return the width of our stack of ingredients
     pub fn width(&self, game: &dyn BaseGame) -> f64 {
         let mut cur_max: f64 = 0.0;
-        let imgs = game.painter().images();
+        let painter = game.painter();
         for ing in self.ingredients.iter() {
-            cur_max = cur_max.max(imgs.image_width(&ing.image));
+            cur_max = cur_max.max(painter.image_width(&ing.image));
         }
 
         cur_max
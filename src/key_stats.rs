@@ -0,0 +1,154 @@
+use serde::{Serialize,Deserialize};
+
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AdaptiveWordsGameConfig {
+    pub strength: f64,    // how strongly new words favor the player's weak keys.  0 turns it off.
+    pub min_presses: u32, // times a letter or bigram must be typed before it can count as weak
+}
+
+/// How the player does at typing one letter or bigram
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct KeyRecord {
+    pub presses: u32,       // times it was meant to be typed
+    pub errors: u32,        // times a different key was typed instead
+    pub total_time: f64,    // seconds since the previous key in the word, summed
+    pub timed_presses: u32, // presses that had a previous key in the word
+}
+
+impl KeyRecord {
+    pub fn error_rate(&self) -> f64 {
+        if self.presses == 0 {
+            return 0.0;
+        }
+
+        self.errors as f64 / self.presses as f64
+    }
+
+    pub fn average_time(&self) -> Option<f64> {
+        if self.timed_presses == 0 {
+            return None;
+        }
+
+        Some(self.total_time / self.timed_presses as f64)
+    }
+}
+
+/// Typing performance for each letter, and each pair of letters typed one after the other
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct KeyStats {
+    keys: BTreeMap<String, KeyRecord>, // keyed by the letter or bigram, sorted so that sums over
+                                       // them always come out the same
+}
+
+impl KeyStats {
+    /// Record the player meaning to type 'expected' after 'prev' (if it was in the same word), and
+    /// whether they got it right.  'interval' is the seconds since 'prev' was typed.
+    pub fn record(&mut self, expected: char, prev: Option<char>, correct: bool, interval: Option<f64>) {
+        let mut keys = vec![expected.to_string()];
+        if let Some(prev) = prev {
+            keys.push([prev, expected].iter().collect());
+        }
+
+        for key in keys.into_iter() {
+            let record = self.keys.entry(key).or_default();
+            record.presses += 1;
+            if !correct {
+                record.errors += 1;
+            }
+            if let Some(interval) = interval {
+                record.total_time += interval;
+                record.timed_presses += 1;
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&KeyRecord> {
+        self.keys.get(key)
+    }
+
+    /// Return how weak the player is at each letter and bigram typed at least 'min_presses' times.
+    /// Weakness is the error rate, plus how much slower than average it's typed (as a fraction of
+    /// the average for all letters, or all bigrams).
+    pub fn weaknesses(&self, min_presses: u32) -> HashMap<String, f64> {
+        // Average time of letters (index 0) and bigrams (index 1)
+        let mut totals = [(0.0, 0); 2];
+        for (key, record) in self.keys.iter() {
+            let total = &mut totals[(key.chars().count() - 1).min(1)];
+            total.0 += record.total_time;
+            total.1 += record.timed_presses;
+        }
+
+        let mut ret: HashMap<String, f64> = HashMap::new();
+        for (key, record) in self.keys.iter() {
+            if record.presses < min_presses {
+                continue;
+            }
+
+            let mut weakness = record.error_rate();
+
+            let (total_time, timed_presses) = totals[(key.chars().count() - 1).min(1)];
+            if let Some(average_time) = record.average_time() {
+                if total_time > 0.0 {
+                    let overall_time = total_time / timed_presses as f64;
+                    weakness += (average_time / overall_time - 1.0).max(0.0);
+                }
+            }
+
+            if weakness > 0.0 {
+                ret.insert(key.clone(), weakness);
+            }
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weaknesses() {
+        let mut stats = KeyStats::default();
+        for _ in 0..4 {
+            stats.record('a', None, true, None);
+            stats.record('b', Some('a'), true, Some(0.1));
+            stats.record('c', Some('b'), true, Some(0.3));
+        }
+        stats.record('c', Some('b'), false, None);
+
+        let weaknesses = stats.weaknesses(2);
+
+        // 'c' is slower than average and was mistyped once.  'a' is never timed, so it's only as
+        // weak as its error rate, which is 0.
+        assert!((weaknesses["c"] - (0.2 + 0.5)).abs() < 1e-9);
+        assert!((weaknesses["bc"] - (0.2 + 0.5)).abs() < 1e-9);
+        assert!(!weaknesses.contains_key("a"));
+        assert!(!weaknesses.contains_key("b"));
+    }
+
+    #[test]
+    fn min_presses() {
+        let mut stats = KeyStats::default();
+        stats.record('q', None, false, None);
+
+        assert!(stats.weaknesses(2).is_empty());
+        assert_eq!(stats.weaknesses(1)["q"], 1.0);
+    }
+
+    #[test]
+    fn serialized_in_order() {
+        let mut stats = KeyStats::default();
+        for c in ['z', 'm', 'a'] {
+            stats.record(c, None, true, None);
+        }
+
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.find("\"a\"").unwrap() < json.find("\"m\"").unwrap());
+        assert!(json.find("\"m\"").unwrap() < json.find("\"z\"").unwrap());
+    }
+}
@@ -1,15 +1,13 @@
+use crate::alphabet::AlphabetGameConfig;
 use crate::painter::{BackgroundConfig, TextConfig};
+use crate::stats::Stats;
 use crate::traits::BaseGame;
+use crate::utils::log;
 
 use engine_p::interpolable::{Interpolable, Pos2d};
 use serde::{Serialize,Deserialize};
-use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
+use std::rc::Rc;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct KeywordEntryUiConfig {
@@ -17,11 +15,48 @@ pub struct KeywordEntryUiConfig {
     pub caret_speed: f64,
     pub bg: BackgroundConfig,
     pub text: TextConfig,
+    pub typo_text: TextConfig, // used instead of 'text' while there's a typo, or input is locked
+}
+
+/// What happens when the player makes a typo
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum TypoPenalty {
+    None,
+    Money(i32),   // lose this much money
+    Lockout(f64), // ignore typing for this many seconds
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct KeywordEntryGameConfig {
+    pub typo_penalty: TypoPenalty,
+}
+
+/// The keywords a key press is checked against
+pub struct KeyContext<'a> {
+    pub keywords: &'a [Rc<String>], // every visible keyword
+    pub targets: &'a [Rc<String>],  // keywords that finish a command, and so can be auto-submitted
+    pub auto_submit: bool,
+    pub alphabet: &'a AlphabetGameConfig, // how typed text is matched against the keywords
+}
+
+pub struct KeyResult {
+    pub command_entered: bool, // Enter was pressed, or the line was auto-submitted
+    pub auto_submitted: bool,  // the command was entered by typing a keyword, not by Enter
+    pub typo: bool,            // this key made the current word stop matching any keyword
 }
 
+// Number of entered lines that can be recalled with Up and Down
+const HISTORY_LEN: usize = 20;
+
 pub struct KeywordEntry {
     entered_text: String,
+    caret: usize,                // byte offset of the caret in 'entered_text'
+    history: Vec<String>,        // previously entered lines, oldest first
+    history_idx: Option<usize>,  // line of 'history' being shown, if any
+    draft: String,               // what was being typed before browsing the history
     caret_timer: Interpolable<f64>,
+    has_typo: bool,     // the current word isn't the start of any keyword
+    lockout_time: f64,  // seconds until typing is accepted again
 }
 
 impl KeywordEntry {
@@ -30,18 +65,28 @@ impl KeywordEntry {
         timer.set_end(1.0);
 
         KeywordEntry {
-            entered_text: "|".to_string(),
+            entered_text: String::new(),
+            caret: 0,
+            history: Vec::new(),
+            history_idx: None,
+            draft: String::new(),
             caret_timer: timer,
+            has_typo: false,
+            lockout_time: 0.0,
         }
     }
 
     /// Reset our state for the start of a new day
     pub fn reset_state(&mut self) {
-        self.entered_text.clear();
-        self.entered_text.push('|');
+        self.set_text(String::new());
+        self.history_idx = None;
+        self.has_typo = false;
+        self.lockout_time = 0.0;
     }
 
     pub fn think(&mut self, game: &dyn BaseGame) {
+        self.lockout_time = (self.lockout_time - game.elapsed_time()).max(0.0);
+
         if self.caret_timer.advance(game.elapsed_time()) {
             if self.caret_timer.cur() == 0.0 {
                 self.caret_timer.set_end(1.0);
@@ -53,49 +98,557 @@ impl KeywordEntry {
     }
 
     pub fn draw(&self, cfg_ui: &KeywordEntryUiConfig, game: &dyn BaseGame) {
-        // Decide whether we want to include the ending '|' in the text.  This serves as our caret.
-        let to_draw: &str;
+        let text_cfg = if self.has_typo || self.is_locked() { &cfg_ui.typo_text } else { &cfg_ui.text };
+
+        game.painter().draw_area_background(&cfg_ui.pos, &cfg_ui.bg);
+        game.painter().draw_text(&self.entered_text, &cfg_ui.pos, cfg_ui.bg.width, text_cfg);
+
+        // Blink the caret
         if self.caret_timer.cur() >= 0.5 {
-            to_draw = &self.entered_text;
+            game.painter().draw_caret(&self.entered_text[..self.caret], &cfg_ui.pos, text_cfg);
+        }
+    }
+
+    /// Return whether typing is being ignored as a typo penalty
+    pub fn is_locked(&self) -> bool {
+        self.lockout_time > 0.0
+    }
+
+    // Replace the entered text with the specified 'text', with the caret at its end
+    fn set_text(&mut self, text: String) {
+        self.entered_text = text;
+        self.caret = self.entered_text.len();
+    }
+
+    // Return the byte offset of the character before the caret
+    fn prev_char(&self) -> Option<usize> {
+        self.entered_text[..self.caret].char_indices().last().map(|(idx, _)| idx)
+    }
+
+    // Return the byte offset of the character after the caret
+    fn next_char(&self) -> Option<usize> {
+        self.entered_text[self.caret..].chars().next().map(|c| self.caret + c.len_utf8())
+    }
+
+    // Return the byte offset of the start of the word before the caret, skipping any whitespace
+    // between them
+    fn prev_word(&self) -> usize {
+        let before = self.entered_text[..self.caret].trim_end();
+        match before.rfind(char::is_whitespace) {
+            Some(idx) => idx + before[idx..].chars().next().unwrap().len_utf8(),
+            None => 0,
         }
-        else {
-            to_draw = &self.entered_text[..self.entered_text.len()-1];
+    }
+
+    // Show the line of 'history' at the specified 'idx', or the draft if 'idx' is 'None'
+    fn recall_history(&mut self, idx: Option<usize>) {
+        if self.history_idx.is_none() {
+            self.draft = self.entered_text.clone();
         }
 
-        game.painter().draw_area_background(&cfg_ui.pos, &cfg_ui.bg);
-        game.painter().draw_text(to_draw, &cfg_ui.pos, cfg_ui.bg.width, &cfg_ui.text);
+        self.history_idx = idx;
+        let text = match idx {
+            Some(idx) => self.history[idx].clone(),
+            None => self.draft.clone(),
+        };
+        self.set_text(text);
+    }
+
+    // Return the endings of the specified 'text' that start at a word, longest first, with runs of
+    // whitespace collapsed to single spaces.  These are what might be the start of a keyword,
+    // since keywords can be phrases of several words.  If 'text' is empty or ends with
+    // whitespace, the last ending is empty.
+    fn tails(text: &str) -> Vec<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let trailing = if text.ends_with(char::is_whitespace) && !words.is_empty() { " " } else { "" };
+
+        let mut ret: Vec<String> = (0..words.len())
+            .map(|i| words[i..].join(" ") + trailing)
+            .collect();
+        if text.trim().is_empty() || !trailing.is_empty() {
+            ret.push(String::new());
+        }
+
+        ret
+    }
+
+    /// Split the specified 'text' into the keywords typed, joining consecutive words that make up
+    /// one of the phrase keywords in 'ctx'.  Words at the end of 'text' are also joined if they're
+    /// the start of a phrase, as it may still be being typed.
+    pub fn tokens(text: &str, ctx: &KeyContext) -> Vec<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+
+        let mut ret: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < words.len() {
+            let mut len = 1;
+            for k in (2..=words.len() - i).rev() {
+                let phrase = words[i..i + k].join(" ");
+                let at_end = i + k == words.len();
+                if ctx.keywords.iter().any(|kw| ctx.alphabet.matches(&phrase, kw) || (at_end && ctx.alphabet.is_prefix(&phrase, kw))) {
+                    len = k;
+                    break;
+                }
+            }
+
+            ret.push(words[i..i + len].join(" "));
+            i += len;
+        }
+
+        ret
+    }
+
+    // Return whether what's before the caret ends with the start of one of the keywords in 'ctx'.
+    // Whitespace always does, since it may start a new word.
+    fn current_word_matches(&self, ctx: &KeyContext) -> bool {
+        let text = &self.entered_text[..self.caret];
+        if text.trim().is_empty() || text.ends_with(char::is_whitespace) {
+            return true;
+        }
+
+        Self::tails(text).iter().any(|tail| ctx.keywords.iter().any(|kw| ctx.alphabet.is_prefix(tail, kw)))
+    }
+
+    // Return the letter before the caret, normalized for matching, if it's part of the word being
+    // typed
+    fn prev_letter(&self, alphabet: &AlphabetGameConfig) -> Option<char> {
+        self.entered_text[..self.caret].chars().last().filter(|c| !c.is_whitespace()).map(|c| alphabet.normalize_char(c))
     }
 
-    pub fn handle_key(&mut self, key: &str, entered_keywords: &mut Vec<String>) -> bool {
-        if key.len() == 1 {
-            self.entered_text.pop();
-            self.entered_text.push(key.chars().nth(0).unwrap());
-            self.entered_text.push('|');
+    // Return the letter, normalized for matching, that should be typed next to continue what's
+    // before the caret towards one of the keywords in 'ctx', if they all agree on it
+    fn expected_letter(&self, ctx: &KeyContext) -> Option<char> {
+        let mut expected: Option<char> = None;
+        for tail in Self::tails(&self.entered_text[..self.caret]).iter() {
+            let tail_chars = tail.chars().count();
+            for kw in ctx.keywords.iter().filter(|kw| ctx.alphabet.is_prefix(tail, kw)) {
+                let next = ctx.alphabet.normalize_char(kw.chars().nth(tail_chars)?);
+                if next.is_whitespace() || expected.is_some_and(|e| e != next) {
+                    return None;
+                }
+                expected = Some(next);
+            }
+        }
+
+        expected
+    }
+
+    // Return how many bytes of the entered text should be auto-submitted after the specified
+    // 'key' was typed, if any.  A target keyword is submitted once it's typed, unless a longer
+    // keyword starts with it, in which case we wait to see whether the next key continues it.
+    fn auto_submit_len(&self, key: &str, ctx: &KeyContext) -> Option<usize> {
+        if self.caret != self.entered_text.len() {
+            return None;
+        }
+
+        // Whether 'text' ends with a whole target
+        let completes = |text: &str| Self::tails(text.trim_end()).iter()
+            .any(|tail| ctx.targets.iter().any(|kw| ctx.alphabet.matches(tail, kw)));
+        // Whether an ending of 'text' longer than 'min_chars' is the start of a keyword, or
+        // strictly the start if 'strict' is set
+        let continues = |text: &str, min_chars: usize, strict: bool| Self::tails(text).iter()
+            .filter(|tail| tail.chars().count() > min_chars)
+            .any(|tail| ctx.keywords.iter().any(|kw| ctx.alphabet.is_prefix(tail, kw) && !(strict && ctx.alphabet.matches(tail, kw))));
+
+        let text = &self.entered_text;
+        if completes(text) && !continues(text, 0, true) {
+            return Some(text.len());
+        }
+
+        // The key may have started something new, if it didn't continue a complete target
+        let before = &text[..text.len() - key.len()];
+        if completes(before) && !continues(text, 1, false) {
+            return Some(before.len());
         }
-        else if key == "Backspace" {
-            if self.entered_text.len() > 1 {
-                self.entered_text.pop();
-                self.entered_text.pop();
-                self.entered_text.push('|');
+
+        None
+    }
+
+    // Enter the first 'len' bytes of the entered text as a command, keeping the rest
+    fn submit(&mut self, len: usize, ctx: &KeyContext, entered_keywords: &mut Vec<String>) {
+        let rest = self.entered_text.split_off(len);
+        let line = std::mem::take(&mut self.entered_text);
+        entered_keywords.splice(.., Self::tokens(&line, ctx));
+
+        if !line.trim().is_empty() && self.history.last() != Some(&line) {
+            self.history.push(line);
+            if self.history.len() > HISTORY_LEN {
+                self.history.remove(0);
             }
         }
-        else if key != "Enter" {
-            log(&format!("Unhandled key: {}", key));
-            return false;
+
+        self.set_text(rest);
+        self.history_idx = None;
+        self.has_typo = false;
+    }
+
+    // Report a typo in 'ret', locking out typing for a while if that's the penalty
+    fn flag_typo(&mut self, ret: &mut KeyResult, cfg_game: &KeywordEntryGameConfig) {
+        ret.typo = true;
+        if let TypoPenalty::Lockout(seconds) = cfg_game.typo_penalty {
+            self.lockout_time = seconds;
         }
+    }
+
+    /// Handle the specified 'key' being pressed, while the keywords in 'ctx' are visible.  Key
+    /// names are those of JS 'KeyboardEvent.key', with "Ctrl+" prepended when Ctrl is held.
+    pub fn handle_key(&mut self, key: &str, ctx: &KeyContext, entered_keywords: &mut Vec<String>, stats: &mut Stats, cfg_game: &KeywordEntryGameConfig) -> KeyResult {
+        let mut ret = KeyResult {
+            command_entered: false,
+            auto_submitted: false,
+            typo: false,
+        };
 
-        entered_keywords.splice(.., self.entered_text[..self.entered_text.len()-1].split_whitespace().map(String::from));
+        if self.is_locked() {
+            return ret;
+        }
+
+        stats.record_key(key, ctx.auto_submit);
+
+        // The letter typed, the one expected, and the one before it, for tracking weak keys
+        let mut letter: Option<(char, Option<char>, Option<char>)> = None;
+        let had_typo = self.has_typo;
+
+        let mut inserted = false;
+        match key {
+            _ if key.chars().count() == 1 => {
+                let typed = key.chars().next().unwrap();
+                if !typed.is_whitespace() && !had_typo {
+                    letter = Some((ctx.alphabet.normalize_char(typed), self.expected_letter(ctx), self.prev_letter(ctx.alphabet)));
+                }
+
+                self.entered_text.insert_str(self.caret, key);
+                self.caret += key.len();
+                inserted = true;
+            }
+            "Backspace" => {
+                if let Some(prev) = self.prev_char() {
+                    self.entered_text.replace_range(prev..self.caret, "");
+                    self.caret = prev;
+                }
+            }
+            "Ctrl+Backspace" => {
+                let start = self.prev_word();
+                self.entered_text.replace_range(start..self.caret, "");
+                self.caret = start;
+            }
+            "Delete" => {
+                if let Some(next) = self.next_char() {
+                    self.entered_text.replace_range(self.caret..next, "");
+                }
+            }
+            "ArrowLeft" => self.caret = self.prev_char().unwrap_or(self.caret),
+            "ArrowRight" => self.caret = self.next_char().unwrap_or(self.caret),
+            "Home" => self.caret = 0,
+            "End" => self.caret = self.entered_text.len(),
+            "Escape" => {
+                self.set_text(String::new());
+                self.history_idx = None;
+            }
+            "ArrowUp" => {
+                let idx = match self.history_idx {
+                    Some(idx) => idx.saturating_sub(1),
+                    None => self.history.len().wrapping_sub(1),
+                };
+                if idx < self.history.len() {
+                    self.recall_history(Some(idx));
+                }
+            }
+            "ArrowDown" => {
+                if let Some(idx) = self.history_idx {
+                    let next = if idx + 1 < self.history.len() { Some(idx + 1) } else { None };
+                    self.recall_history(next);
+                }
+            }
+            "Enter" => {}
+            _ => {
+                log(&format!("Unhandled key: {}", key));
+                return ret;
+            }
+        }
 
         if key == "Enter" {
-            self.entered_text.clear();
-            self.entered_text.push('|');
-            return true;
+            // Entering a line with no keywords in it is a mistake too, if it wasn't already
+            // flagged while it was typed
+            let tokens = Self::tokens(&self.entered_text, ctx);
+            let matches_none = !tokens.is_empty()
+                && !tokens.iter().any(|token| ctx.keywords.iter().any(|kw| ctx.alphabet.matches(token, kw)));
+            if matches_none && !had_typo {
+                self.flag_typo(&mut ret, cfg_game);
+            }
+
+            self.submit(self.entered_text.len(), ctx, entered_keywords);
+            ret.command_entered = true;
+            return ret;
         }
 
-        false
+        if ctx.auto_submit && inserted {
+            if let Some(len) = self.auto_submit_len(key, ctx) {
+                if let Some((typed, expected, prev)) = letter {
+                    stats.record_letter(typed, expected, prev, false);
+                }
+
+                self.submit(len, ctx, entered_keywords);
+                ret.command_entered = true;
+                ret.auto_submitted = true;
+                return ret;
+            }
+        }
+
+        entered_keywords.splice(.., Self::tokens(&self.entered_text, ctx));
+
+        // Only the key that breaks the match is a typo, not the ones typed after it
+        self.has_typo = !self.current_word_matches(ctx);
+        if self.has_typo && !had_typo && inserted {
+            self.flag_typo(&mut ret, cfg_game);
+        }
+
+        if let Some((typed, expected, prev)) = letter {
+            stats.record_letter(typed, expected, prev, ret.typo);
+        }
+
+        ret
+    }
+
+    /// Return the keywords typed so far, with phrases joined as in 'tokens'
+    pub fn typed_tokens(&self, ctx: &KeyContext) -> Vec<String> {
+        Self::tokens(&self.entered_text, ctx)
     }
 
     pub fn update_config(&mut self, cfg_ui: &KeywordEntryUiConfig) {
         self.caret_timer.set_speed(cfg_ui.caret_speed);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_config;
+
+    // A keyword entry with the specified visible 'keywords', all of which finish a command
+    struct Fixture {
+        entry: KeywordEntry,
+        keywords: Vec<Rc<String>>,
+        alphabet: AlphabetGameConfig,
+        auto_submit: bool,
+        cfg_game: KeywordEntryGameConfig,
+        stats: Stats,
+        entered_keywords: Vec<String>,
+    }
+
+    impl Fixture {
+        fn new(keywords: &[&str]) -> Self {
+            let cfg = test_config();
+            Fixture {
+                entry: KeywordEntry::new(&cfg.ui.keyword_entry),
+                keywords: keywords.iter().map(|kw| Rc::new(kw.to_string())).collect(),
+                alphabet: cfg.game.alphabet.clone(),
+                auto_submit: false,
+                cfg_game: KeywordEntryGameConfig { typo_penalty: TypoPenalty::None },
+                stats: Stats::new(),
+                entered_keywords: Vec::new(),
+            }
+        }
+
+        // Press each of 'keys', returning the result of the last one
+        fn press(&mut self, keys: &[&str]) -> KeyResult {
+            let ctx = KeyContext {
+                keywords: &self.keywords,
+                targets: &self.keywords,
+                auto_submit: self.auto_submit,
+                alphabet: &self.alphabet,
+            };
+
+            let mut ret = None;
+            for key in keys {
+                ret = Some(self.entry.handle_key(key, &ctx, &mut self.entered_keywords, &mut self.stats, &self.cfg_game));
+            }
+            ret.unwrap()
+        }
+
+        // Type each character of 'text', returning the result of the last one
+        fn type_text(&mut self, text: &str) -> KeyResult {
+            let keys: Vec<String> = text.chars().map(|c| c.to_string()).collect();
+            self.press(&keys.iter().map(|k| k.as_str()).collect::<Vec<&str>>())
+        }
+
+        fn text(&self) -> &str {
+            &self.entry.entered_text
+        }
+    }
+
+    #[test]
+    fn caret_editing() {
+        let mut f = Fixture::new(&[]);
+        f.type_text("abc");
+        f.press(&["ArrowLeft"]);
+        f.type_text("x");
+        assert_eq!(f.text(), "abxc");
+
+        f.press(&["Home"]);
+        f.type_text("y");
+        assert_eq!(f.text(), "yabxc");
+
+        f.press(&["End", "Backspace"]);
+        assert_eq!(f.text(), "yabx");
+
+        f.press(&["Home", "Delete"]);
+        assert_eq!(f.text(), "abx");
+
+        // Moving past either end stays there
+        f.press(&["ArrowLeft", "ArrowLeft", "Backspace"]);
+        assert_eq!(f.text(), "abx");
+        f.press(&["End", "ArrowRight", "Delete"]);
+        assert_eq!(f.text(), "abx");
+
+        f.press(&["Escape"]);
+        assert_eq!(f.text(), "");
+    }
+
+    #[test]
+    fn ctrl_backspace_deletes_word() {
+        let mut f = Fixture::new(&[]);
+        f.type_text("one two  ");
+        f.press(&["Ctrl+Backspace"]);
+        assert_eq!(f.text(), "one ");
+
+        f.press(&["Ctrl+Backspace"]);
+        assert_eq!(f.text(), "");
+    }
+
+    #[test]
+    fn multibyte_caret() {
+        let mut f = Fixture::new(&[]);
+        f.type_text("café");
+        f.press(&["ArrowLeft", "Backspace"]);
+        assert_eq!(f.text(), "caé");
+
+        f.press(&["Delete"]);
+        assert_eq!(f.text(), "ca");
+    }
+
+    #[test]
+    fn history() {
+        let mut f = Fixture::new(&[]);
+        f.type_text("one");
+        f.press(&["Enter"]);
+        f.type_text("two");
+        f.press(&["Enter"]);
+        f.type_text("dr");
+
+        f.press(&["ArrowUp"]);
+        assert_eq!(f.text(), "two");
+        f.press(&["ArrowUp", "ArrowUp"]);
+        assert_eq!(f.text(), "one");
+        f.press(&["ArrowDown"]);
+        assert_eq!(f.text(), "two");
+        f.press(&["ArrowDown"]);
+        assert_eq!(f.text(), "dr");
+    }
+
+    #[test]
+    fn typo_only_flags_first_bad_key() {
+        let mut f = Fixture::new(&["apple", "banana"]);
+        assert!(!f.type_text("ap").typo);
+        assert!(f.type_text("x").typo);
+        assert!(!f.type_text("y").typo);
+        assert!(f.entry.has_typo);
+
+        // Fixing the word clears the typo, and a new word can start after a space
+        f.press(&["Backspace", "Backspace"]);
+        assert!(!f.entry.has_typo);
+        assert!(!f.type_text("ple ban").typo);
+        assert!(f.type_text("q").typo);
+    }
+
+    #[test]
+    fn enter_without_keyword_is_typo() {
+        let mut f = Fixture::new(&["apple", "banana"]);
+
+        // Trailing whitespace keeps the line matching until it's entered
+        assert!(!f.type_text("ban ").typo);
+        assert!(f.press(&["Enter"]).typo);
+
+        // A line with a keyword in it is fine, as is an empty one
+        f.type_text("apple ban");
+        assert!(!f.press(&["Enter"]).typo);
+        assert!(!f.press(&["Enter"]).typo);
+
+        // A typo already flagged while typing isn't counted again
+        assert!(f.type_text("x").typo);
+        assert!(!f.press(&["Enter"]).typo);
+    }
+
+    #[test]
+    fn lockout_ignores_typing() {
+        let mut f = Fixture::new(&["apple"]);
+        f.cfg_game.typo_penalty = TypoPenalty::Lockout(1.0);
+
+        assert!(f.type_text("x").typo);
+        assert!(f.entry.is_locked());
+        f.press(&["Backspace"]);
+        f.type_text("a");
+        assert_eq!(f.text(), "x");
+    }
+
+    #[test]
+    fn auto_submit_exact_match() {
+        let mut f = Fixture::new(&["dog", "cat"]);
+        f.auto_submit = true;
+
+        assert!(!f.type_text("do").command_entered);
+        let ret = f.type_text("g");
+        assert!(ret.command_entered && ret.auto_submitted);
+        assert_eq!(f.entered_keywords, vec!["dog"]);
+        assert_eq!(f.text(), "");
+    }
+
+    #[test]
+    fn auto_submit_waits_for_longer_keyword() {
+        let mut f = Fixture::new(&["cat", "catch"]);
+        f.auto_submit = true;
+
+        // "cat" could still become "catch"
+        assert!(!f.type_text("cat").command_entered);
+        assert!(!f.type_text("c").command_entered);
+        assert!(f.type_text("h").auto_submitted);
+        assert_eq!(f.entered_keywords, vec!["catch"]);
+
+        // A key that doesn't continue "catch" submits "cat", and is kept for what comes next
+        f.type_text("cat");
+        assert!(f.type_text("x").auto_submitted);
+        assert_eq!(f.entered_keywords, vec!["cat"]);
+        assert_eq!(f.text(), "x");
+
+        // So does a space
+        f.press(&["Escape"]);
+        f.type_text("cat");
+        assert!(f.type_text(" ").auto_submitted);
+        assert_eq!(f.entered_keywords, vec!["cat"]);
+    }
+
+    #[test]
+    fn auto_submit_only_at_end() {
+        let mut f = Fixture::new(&["dog"]);
+        f.auto_submit = true;
+
+        f.type_text("og");
+        f.press(&["Home"]);
+        assert!(!f.type_text("d").command_entered);
+        assert_eq!(f.text(), "dog");
+    }
+
+    #[test]
+    fn tokens_join_phrases() {
+        let f = Fixture::new(&["ice cream", "tea"]);
+        let ctx = KeyContext {
+            keywords: &f.keywords,
+            targets: &f.keywords,
+            auto_submit: false,
+            alphabet: &f.alphabet,
+        };
+
+        assert_eq!(KeywordEntry::tokens("ice  cream tea", &ctx), vec!["ice cream", "tea"]);
+        assert_eq!(KeywordEntry::tokens("ice tea", &ctx), vec!["ice", "tea"]);
+        assert_eq!(KeywordEntry::tokens("tea ice cr", &ctx), vec!["tea", "ice cr"]);
+    }
+}
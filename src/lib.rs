@@ -1,42 +1,63 @@
+mod alphabet;
+mod draw_backend;
+mod events;
 mod ingredients;
 mod ingredient_area;
+mod key_stats;
 mod keyword_entry;
 mod order_bar;
 mod painter;
 mod preparation_area;
+mod recording;
+mod reputation;
+mod save_game;
 mod state_area;
+mod stats;
 mod store;
+mod streak;
 mod traits;
 mod utils;
+mod validation;
+mod word_score;
+mod word_source;
 
+use alphabet::AlphabetGameConfig;
+use draw_backend::{CanvasBackend, HeadlessBackend};
 use ingredient_area::{IngredientArea, IngredientAreaGameConfig, IngredientAreaUiConfig};
 use ingredients::MovableIngredient;
 use engine_p::images::{Images, ImagesConfig};
 use engine_p::interpolable::Pos2d;
-use engine_p::sounds::{Sounds, SoundsConfig};
+use engine_p::sounds::{PlaybackConfig, Sounds, SoundsConfig};
 use js_sys::JsString;
-use keyword_entry::{KeywordEntry, KeywordEntryUiConfig};
+use key_stats::AdaptiveWordsGameConfig;
+use keyword_entry::{KeyContext, KeywordEntry, KeywordEntryGameConfig, KeywordEntryUiConfig, TypoPenalty};
 use order_bar::{OrderBar, OrderBarGameConfig, OrderBarUiConfig};
 use painter::{BackgroundConfig, Painter, TextConfig};
 use preparation_area::{PreparationArea, PreparationAreaConfig};
+use recording::{Replay, ReplayInput};
+use save_game::SAVE_VERSION;
 use serde::{Serialize,Deserialize};
-use state_area::{StateArea, StateGameConfig, StateUiConfig};
+use state_area::{DaySummaryUiConfig, StateArea, StateGameConfig, StateUiConfig};
+use reputation::{ReputationGameConfig, ReputationUiConfig};
+use stats::{Stats, StatsReport};
 use store::{StoreConfig, StoreUpgradeAction, StoreUpgradeConfig, UpgradeStore};
+use streak::{StreakGameConfig, StreakIndicator, StreakUiConfig};
 use traits::{BaseGame, Image, Sound};
-use utils::{set_panic_hook, WordBank};
+use utils::{log, random_seed, set_panic_hook, KeywordAllocation, Rng, WordBank};
+use validation::{ConfigIssue, Severity};
 use wasm_bindgen::prelude::*;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, OffscreenCanvas, OffscreenCanvasRenderingContext2d};
+use web_sys::HtmlCanvasElement;
 use web_time::Instant;
+use word_score::{WordLevelConfig, WordScoreConfig};
+use word_source::{load_words, WordPoolsConfig, WordSourceConfig};
 
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
+pub use events::GameEvent;
+pub use recording::Recording;
+pub use save_game::SaveGame;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MoneyUiConfig {
@@ -51,6 +72,23 @@ pub struct MoneyGameConfig {
     pub max_money: i32,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TimestepGameConfig {
+    pub tick_seconds: f64,          // simulation time advanced by each tick
+    pub max_ticks_per_frame: usize, // limit on ticks run to catch up after a slow frame
+}
+
+/// Settings that vary with the difficulty the player picked
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DifficultyGameConfig {
+    pub auto_submit: bool, // enter commands as soon as a keyword is typed, without Enter
+    pub keyword_allocation: KeywordAllocation,
+    pub word_levels: Vec<WordLevelConfig>, // score ranges new words are picked from, by weight
+    pub alphabet: Option<AlphabetGameConfig>, // replaces the game's 'alphabet' if set
+    pub word_pools: Option<WordPoolsConfig>,  // replaces the game's 'word_pools' if set
+    pub strict_assembly: bool, // orders with 'strict_order' must be assembled bottom to top
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct UiConfig {
     pub images: ImagesConfig<Image>,
@@ -59,20 +97,50 @@ pub struct UiConfig {
     pub ingredient_area: IngredientAreaUiConfig,
     pub preparation_area: PreparationAreaConfig,
     pub money: MoneyUiConfig,
+    pub reputation: ReputationUiConfig,
+    pub streak: StreakUiConfig,
     pub store: StoreConfig,
     pub keyword_entry: KeywordEntryUiConfig,
     pub fps: TextConfig,
     pub state: StateUiConfig,
+    pub day_summary: DaySummaryUiConfig,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct GameConfig {
-    pub word_level: i32,
+    pub difficulty: usize,
     pub unlock_all: bool,
     pub ingredient_area: IngredientAreaGameConfig,
     pub order_bar: OrderBarGameConfig,
     pub state: StateGameConfig,
     pub money: MoneyGameConfig,
+    pub reputation: ReputationGameConfig,
+    pub streak: StreakGameConfig,
+    pub keyword_entry: KeywordEntryGameConfig,
+    pub timestep: TimestepGameConfig,
+    pub difficulties: Vec<DifficultyGameConfig>, // indexed by 'difficulty'
+    pub word_score: WordScoreConfig,
+    pub adaptive_words: AdaptiveWordsGameConfig,
+    pub alphabet: AlphabetGameConfig,
+    pub word_sources: Vec<WordSourceConfig>,
+    pub word_pools: WordPoolsConfig,
+}
+
+impl GameConfig {
+    /// Return the settings for the current 'difficulty'
+    pub fn difficulty(&self) -> &DifficultyGameConfig {
+        &self.difficulties[self.difficulty.min(self.difficulties.len() - 1)]
+    }
+
+    /// Return the alphabet used at the current 'difficulty'
+    pub fn alphabet(&self) -> &AlphabetGameConfig {
+        self.difficulty().alphabet.as_ref().unwrap_or(&self.alphabet)
+    }
+
+    /// Return the word pools used at the current 'difficulty'
+    pub fn word_pools(&self) -> &WordPoolsConfig {
+        self.difficulty().word_pools.as_ref().unwrap_or(&self.word_pools)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -81,14 +149,24 @@ pub struct OuterConfig {
     pub game: GameConfig,
 }
 
+// Return the config the page uses, for unit tests to adjust
+#[cfg(test)]
+pub(crate) fn test_config() -> OuterConfig {
+    serde_json::from_str(include_str!("../tests/data/config.json")).expect("tests/data/config.json should parse")
+}
+
 ///////// GameState
 struct GameImp {
     cur_money: RefCell<i32>,
+    cur_reputation: RefCell<f64>,
+    cur_streak: RefCell<u32>,
     words_bank: WordBank,
+    rng: Rng,
     painter: Painter,
-    sounds: Sounds<Sound>,
+    sounds: Option<Sounds<Sound>>, // 'None' when running headless
     config: OuterConfig,
-    elapsed_time: f64,  // seconds since previous frame start (for calculating current frame)
+    elapsed_time: f64,  // seconds simulated by the current tick
+    events: RefCell<Vec<GameEvent>>, // emitted, but not yet dispatched
 }
 
 impl BaseGame for GameImp {
@@ -96,26 +174,30 @@ impl BaseGame for GameImp {
         *self.cur_money.borrow()
     }
 
-    fn add_money(&self, amt: i32) {
-        let money = &mut *self.cur_money.borrow_mut();
-        *money += amt;
-        if *money > self.config.game.money.max_money {
-            *money = self.config.game.money.max_money;
-        }
+    fn get_reputation(&self) -> f64 {
+        *self.cur_reputation.borrow()
+    }
+
+    fn get_streak(&self) -> u32 {
+        *self.cur_streak.borrow()
     }
 
     fn painter<'a>(&'a self) -> &'a Painter {
         &self.painter
     }
 
-    fn sounds(&self) -> &Sounds<Sound> {
-        &self.sounds
+    fn emit(&self, event: GameEvent) {
+        self.events.borrow_mut().push(event);
     }
 
     fn word_bank<'a>(&'a self) -> &'a WordBank {
         &self.words_bank
     }
 
+    fn rng(&self) -> &Rng {
+        &self.rng
+    }
+
     fn elapsed_time(&self) -> f64 {
         self.elapsed_time
     }
@@ -125,11 +207,31 @@ impl GameImp {
     fn think(&mut self) {
         self.painter.think(self.elapsed_time);
     }
+
+    fn add_money(&self, amt: i32) {
+        let money = &mut *self.cur_money.borrow_mut();
+        *money += amt;
+        if *money > self.config.game.money.max_money {
+            *money = self.config.game.money.max_money;
+        }
+    }
+
+    fn add_reputation(&self, amt: f64) {
+        let reputation = &mut *self.cur_reputation.borrow_mut();
+        *reputation = (*reputation + amt).clamp(0.0, self.config.game.reputation.max);
+    }
+
+    fn play_sound(&self, cfg: &PlaybackConfig<Sound>) {
+        if let Some(sounds) = &self.sounds {
+            sounds.play_sound(cfg);
+        }
+    }
 }
 
+// Limit on events kept for 'Game::drain_events', in case nothing is draining them
+const MAX_PENDING_EVENTS: usize = 1000;
+
 struct GameState {
-    screen_canvas: HtmlCanvasElement,
-    offscreen_canvas: OffscreenCanvas,
     order_bar: OrderBar,
     ingredient_area: IngredientArea,
     preparation_area: PreparationArea,
@@ -137,18 +239,86 @@ struct GameState {
     state_area: StateArea,
     keyword_entry: KeywordEntry,
     got_first_input: bool,
-    frame_times: Vec<(Instant, Instant)>, // for measuring elapsed_time, fps
+    frame_times: Vec<(Instant, Instant)>, // for measuring frame time, fps
     fps_str: String,
+    unsimulated_time: f64, // frame time not yet simulated by a tick
+    recording: Recording,
+    replay: Option<Replay>, // set while playing back a recording
+    pending_events: Vec<GameEvent>, // dispatched events, waiting for 'Game::drain_events'
+    stats: Stats,
+    streak_indicator: StreakIndicator,
     imp: GameImp,
 }
 
 impl GameState {
-    fn think(&mut self) {
+    fn new(game_config: OuterConfig, seed: u32, painter: Painter, sounds: Option<Sounds<Sound>>, words_bank: WordBank) -> Self {
+        let order_bar = OrderBar::new(&game_config.ui.order_bar, &game_config.game.order_bar, &game_config.game.reputation);
+
+        let recording = Recording::new(seed, &game_config);
+
+        let game_imp = GameImp {
+            cur_money: RefCell::new(game_config.game.money.starting_money),
+            cur_reputation: RefCell::new(game_config.game.reputation.starting),
+            cur_streak: RefCell::new(0),
+            words_bank: words_bank,
+            rng: Rng::new(seed),
+            painter: painter,
+            sounds: sounds,
+            config: game_config,
+            elapsed_time: 0.0,
+            events: RefCell::new(Vec::new()),
+        };
+
+        let preparation_area = PreparationArea::new(&game_imp, &game_imp.config.ui.preparation_area);
+
+        let ingredient_area = IngredientArea::new(&game_imp, &game_imp.config.ui.ingredient_area, &game_imp.config.game.ingredient_area);
+
+        let store = UpgradeStore::new(&game_imp, &game_imp.config.ui.store);
+
+        let state_area = StateArea::new(&game_imp.config.ui.state, &game_imp.config.game.state, &game_imp);
+
+        let keyword_entry = KeywordEntry::new(&game_imp.config.ui.keyword_entry);
+
+        let streak_indicator = StreakIndicator::new(&game_imp.config.ui.streak);
+
+        let mut state = GameState{
+            order_bar: order_bar,
+            ingredient_area: ingredient_area,
+            preparation_area: preparation_area,
+            store: store,
+            keyword_entry: keyword_entry,
+            got_first_input: false,
+            state_area: state_area,
+            frame_times: Vec::new(),
+            imp: game_imp,
+            fps_str: "".to_string(),
+            unsimulated_time: 0.0,
+            recording: recording,
+            replay: None,
+            pending_events: Vec::new(),
+            stats: Stats::new(),
+            streak_indicator: streak_indicator,
+        };
+
+        state.frame_times.push((Instant::now(), Instant::now()));
+
+        state.update_recipes();
+
+        if state.imp.config.game.unlock_all {
+            let mut upgrades: Vec<StoreUpgradeConfig> = Vec::new();
+            state.store.unlock_all(&mut upgrades, &state.imp.config.ui.store);
+            state.process_store_upgrades(&upgrades);
+        }
+
+        state
+    }
 
+    // Return the wall-clock time between the last two frames, and update the FPS
+    fn update_frame_times(&mut self) -> f64 {
         // Update frame time and FPS status
         let prev_frame = &self.frame_times[self.frame_times.len() - 2];
         let cur_frame = self.frame_times.last().unwrap();
-        self.imp.elapsed_time = (cur_frame.0 - prev_frame.0).as_secs_f64();
+        let frame_time = (cur_frame.0 - prev_frame.0).as_secs_f64();
 
         let frames_per_update = 10;
         if self.frame_times.len() > frames_per_update + 2 {
@@ -162,35 +332,156 @@ impl GameState {
             self.fps_str = format!("{:.2} FPS ({:2.2} %)", fps, processing_pct);
         }
 
+        frame_time
+    }
+
+    // Advance the simulation by 'frame_time', in fixed ticks.  Time left over from a partial tick
+    // carries over to the next frame, and time beyond 'max_ticks_per_frame' is dropped.  While
+    // replaying, the recorded keys and frame time are used instead.
+    fn advance(&mut self, frame_time: f64) {
+        let mut frame_time = frame_time;
+
+        if let Some(replay) = &mut self.replay {
+            let (inputs, replay_time) = replay.next_frame();
+            let done = replay.is_done();
+
+            for input in inputs.iter() {
+                match input {
+                    ReplayInput::Key(key) => self.handle_key(key),
+                    ReplayInput::Config(cfg) => self.change_config(cfg),
+                }
+            }
+
+            if let Some(replay_time) = replay_time {
+                frame_time = replay_time;
+            }
+
+            if done {
+                log("Replay finished");
+                self.replay = None;
+            }
+        }
+
+        self.recording.record_frame(frame_time);
+
+        let cfg = &self.imp.config.game.timestep;
+        let tick_seconds = cfg.tick_seconds;
+        let max_time = tick_seconds * cfg.max_ticks_per_frame as f64;
+        self.unsimulated_time = (self.unsimulated_time + frame_time).min(max_time);
+
+        self.imp.elapsed_time = tick_seconds;
+        while self.unsimulated_time >= tick_seconds {
+            self.unsimulated_time -= tick_seconds;
+            self.think();
+        }
+    }
+
+    // Advance the simulation by one tick.  Doesn't draw anything.
+    fn think(&mut self) {
         self.imp.think();
+        self.imp.words_bank.set_visible(&self.keywords());
 
         self.state_area.think(&self.imp.config.game.state, &self.imp);
         self.keyword_entry.think(&self.imp);
+        self.streak_indicator.think(&self.imp);
 
-        if self.state_area.in_store() {
-            // TODO
-        }
-        else {
-            self.order_bar.think(&self.imp, &self.imp.config.ui.order_bar, &self.imp.config.game.order_bar);
+        if self.state_area.in_restaurant() {
+            self.order_bar.think(&self.imp, &self.imp.config.ui.order_bar, &self.imp.config.game.order_bar, &self.imp.config.game.reputation, &self.imp.config.game.streak);
             self.ingredient_area.think(&self.imp);
             self.preparation_area.think(&self.imp.config.ui.preparation_area, &self.imp);
         }
+
+        self.dispatch_events();
+
+        self.stats.advance(self.imp.elapsed_time, self.state_area.in_restaurant());
+        self.stats.observe_keywords(&self.keywords());
+    }
+
+    // Pass the events emitted since the last dispatch to each of the handlers
+    fn dispatch_events(&mut self) {
+        let events: Vec<GameEvent> = self.imp.events.borrow_mut().drain(..).collect();
+
+        for event in events.iter() {
+            self.handle_money_event(event);
+            self.imp.add_reputation(self.imp.config.game.reputation.change(event));
+            self.handle_streak_event(event);
+            self.handle_sound_event(event);
+            self.stats.handle_event(event);
+        }
+
+        self.pending_events.extend(events);
+        if self.pending_events.len() > MAX_PENDING_EVENTS {
+            let excess = self.pending_events.len() - MAX_PENDING_EVENTS;
+            self.pending_events.drain(..excess);
+        }
+    }
+
+    fn handle_money_event(&self, event: &GameEvent) {
+        match event {
+            GameEvent::OrderServed { price, tip, streak_bonus, .. } => self.imp.add_money(price + tip + streak_bonus),
+            GameEvent::UpgradePurchased { cost, .. } => self.imp.add_money(-cost),
+            GameEvent::OrderExpired { penalty, .. } => self.imp.add_money(-penalty),
+            GameEvent::MoneyDrained { amount } => self.imp.add_money(*amount),
+            GameEvent::Typo =>
+                if let TypoPenalty::Money(amount) = self.imp.config.game.keyword_entry.typo_penalty {
+                    self.imp.add_money(-amount);
+                },
+            _ => {}
+        }
+    }
+
+    fn handle_streak_event(&mut self, event: &GameEvent) {
+        let streak = &mut *self.imp.cur_streak.borrow_mut();
+        match event {
+            GameEvent::OrderServed { .. } => {
+                *streak += 1;
+                self.stats.record_streak(*streak);
+                self.streak_indicator.pulse(&self.imp.config.ui.streak);
+            }
+            GameEvent::Typo | GameEvent::OrderExpired { .. } | GameEvent::OrderMissed => *streak = 0,
+            _ => {}
+        }
+    }
+
+    fn handle_sound_event(&self, event: &GameEvent) {
+        let cookers = &self.imp.config.ui.preparation_area.cookers;
+
+        match event {
+            GameEvent::OrderServed { .. } =>
+                self.imp.play_sound(&self.imp.config.ui.order_bar.money_sound),
+            GameEvent::CookingStarted { cooker_idx, cook_time, .. } =>
+                if let Some(cooker) = cookers.get(*cooker_idx) {
+                    // Play the sound for as long as the cooking takes
+                    let mut snd_cfg = cooker.cooking_sound.clone();
+                    snd_cfg.play_length = Some(*cook_time);
+                    self.imp.play_sound(&snd_cfg);
+                },
+            GameEvent::CookingDone { cooker_idx, .. } =>
+                if let Some(cooker) = cookers.get(*cooker_idx) {
+                    self.imp.play_sound(&cooker.done_cooking_sound);
+                },
+            _ => {}
+        }
     }
 
     fn draw(&self) {
-        self.imp.painter().canvas().set_fill_style_str("DimGrey");
-        self.imp.painter().canvas().clear_rect(0.0, 0.0, 2560.0, 1440.0);
-        self.imp.painter().canvas().fill_rect(0.0, 0.0, 2560.0, 1440.0);
+        self.imp.painter().clear_screen("DimGrey");
 
         self.state_area.draw(&self.imp.config.ui.state, &self.imp.config.game.state, &self.imp);
 
         if self.state_area.in_store() {
             self.store.draw(&self.imp, &self.imp.config.ui.store);
         }
+        else if self.state_area.in_summary() {
+            self.state_area.draw_summary(&self.imp.config.ui.day_summary, self.stats.today(), &self.imp);
+        }
         else {
             self.order_bar.draw(&self.imp, &self.imp.config.ui.order_bar);
             self.ingredient_area.draw(&self.imp, &self.imp.config.ui.ingredient_area);
             self.preparation_area.draw(&self.imp, &self.imp.config.ui.preparation_area);
+
+            let streak = self.imp.get_streak();
+            self.streak_indicator.draw(streak, self.imp.config.game.streak.multiplier(streak), &self.imp.config.ui.streak, &self.imp);
         }
     
         self.keyword_entry.draw(&self.imp.config.ui.keyword_entry, &self.imp);
@@ -206,20 +497,52 @@ impl GameState {
                                 cur_money as f64 / self.imp.config.game.money.max_money as f64,
                                 &self.imp.config.ui.money.text);
 
+        // Draw current reputation
+        let cur_reputation = self.imp.get_reputation();
+        let reputation_cfg = &self.imp.config.ui.reputation;
+        self.imp.painter().draw_area_background(&reputation_cfg.pos, &reputation_cfg.bg);
+        self.imp.painter().draw_text_with_filled(
+                                &format!("★ {:.1}", cur_reputation),
+                                &reputation_cfg.pos,
+                                reputation_cfg.bg.width,
+                                self.imp.config.game.reputation.level(cur_reputation),
+                                &reputation_cfg.text);
+
         // Draw FPS
         self.imp.painter().draw_text(&self.fps_str, &(2000, 10).into(), 300.0, &self.imp.config.ui.fps);
 
-        let screen_context = self.screen_canvas
-        .get_context("2d").unwrap().unwrap()
-        .dyn_into::<CanvasRenderingContext2d>().unwrap();
+        self.imp.painter().present();
+    }
+
+    // Return the keywords currently on screen
+    fn keywords(&self) -> Vec<Rc<String>> {
+        let mut keywords: Vec<Rc<String>> = Vec::new();
+
+        self.state_area.collect_keywords(&mut keywords);
+
+        if self.state_area.in_store() {
+            self.store.collect_keywords(&mut keywords, &self.imp.config.ui.store);
+        }
+        else if self.state_area.in_restaurant() {
+            self.order_bar.collect_keywords(&mut keywords);
+            self.ingredient_area.collect_keywords(&mut keywords);
+            self.preparation_area.collect_keywords(&mut keywords);
+        }
+
+        keywords
+    }
 
-        screen_context.clear_rect(0.0, 0.0, self.screen_canvas.width() as f64, self.screen_canvas.height() as f64);
+    // Return the keywords currently on screen that finish a command.  Ingredients only select
+    // something for a later keyword on the same line to use, so they don't.
+    fn target_keywords(&self) -> Vec<Rc<String>> {
+        let mut ingredients: Vec<Rc<String>> = Vec::new();
+        if self.state_area.in_restaurant() {
+            self.ingredient_area.collect_keywords(&mut ingredients);
+        }
 
-        screen_context.draw_image_with_offscreen_canvas_and_dw_and_dh(
-            &self.offscreen_canvas,
-            0.0, 0.0,
-            self.screen_canvas.width() as f64, self.screen_canvas.height() as f64)
-        .expect("draw offscreen canvas");
+        let mut keywords = self.keywords();
+        keywords.retain(|kw| !ingredients.contains(kw));
+        keywords
     }
 
     fn update_recipes(&mut self) {
@@ -250,10 +573,30 @@ impl GameState {
         self.update_recipes();
     }
 
-    fn handle_command(&mut self) {
-        let keywords = self.imp.painter.entered_keywords().clone();
+    // Return the specified 'typed' words, with each one that matches a visible keyword replaced by
+    // that keyword, so that areas can compare them exactly
+    fn resolve_keywords(&self, typed: &[String]) -> Vec<String> {
+        let visible = self.keywords();
+        let alphabet = self.imp.config.game.alphabet();
+
+        typed.iter()
+            .map(|word| match visible.iter().find(|kw| alphabet.matches(word, kw)) {
+                Some(kw) => kw.to_string(),
+                None => word.clone(),
+            })
+            .collect()
+    }
+
+    fn handle_command(&mut self, auto_submitted: bool) {
+        let typed = self.imp.painter.entered_keywords().clone();
+        let keywords = self.resolve_keywords(&typed);
+        let auto_submit = self.imp.config.game.difficulty().auto_submit;
+        let visible = self.keywords();
+        self.stats.record_command(&keywords, &visible, auto_submit, auto_submitted);
+        self.imp.words_bank.set_visible(&visible);
 
         let was_in_store= self.state_area.in_store();
+        let was_in_restaurant = self.state_area.in_restaurant();
         self.state_area.handle_command(&keywords,&self.imp);
 
         if was_in_store {
@@ -265,12 +608,16 @@ impl GameState {
 
             // If we're not in the store now, then we've transitioned back to the restaurant
             if !self.state_area.in_store() {
-                self.order_bar.reset_state();
+                self.order_bar.reset_state(&self.imp, &self.imp.config.game.order_bar, &self.imp.config.game.reputation);
                 self.preparation_area.reset_state();
                 self.keyword_entry.reset_state();
             }
         }
-        else {
+        else if was_in_restaurant && self.state_area.in_summary() {
+            // The day is over, so any orders still waiting were missed
+            self.order_bar.miss_orders(&self.imp, &self.imp.config.game.reputation, &self.imp.config.game.streak);
+        }
+        else if was_in_restaurant {
             let mut selected_ings: Vec<MovableIngredient> = Vec::new();
 
             self.ingredient_area.handle_command(
@@ -280,164 +627,462 @@ impl GameState {
 
             let handled = self.preparation_area.handle_command(&keywords, &mut selected_ings, &self.imp, &self.imp.config.ui.preparation_area);
             if !handled {
-                self.order_bar.handle_command(&keywords, &mut selected_ings, &self.imp);
+                let strict_assembly = self.imp.config.game.difficulty().strict_assembly;
+                self.order_bar.handle_command(&keywords, &mut selected_ings, strict_assembly, &self.imp, &self.imp.config.ui.order_bar);
             }
         }
+
+        self.update_word_bias();
+
+        self.dispatch_events();
+    }
+
+    // Make new keywords favor the letters and bigrams the player is weakest at
+    fn update_word_bias(&mut self) {
+        let cfg = &self.imp.config.game.adaptive_words;
+        if cfg.strength == 0.0 {
+            return;
+        }
+
+        let bias = self.stats.keys().weaknesses(cfg.min_presses).into_iter()
+            .map(|(key, weakness)| (key, weakness * cfg.strength))
+            .collect();
+        self.imp.words_bank.set_bias(bias);
+    }
+
+    // Handle a key pressed by the user.  Ignored while replaying.
+    fn report_key(&mut self, key: &str) {
+        if self.replay.is_none() {
+            self.handle_key(key);
+        }
     }
 
-    fn handle_key(&mut self, key: &str, _state_rc: &Rc<RefCell<GameState>>) {
+    fn handle_key(&mut self, key: &str) {
+        self.recording.record_key(key);
+
         if !self.got_first_input {
-            self.imp.sounds.handle_first_input();
+            if let Some(sounds) = &mut self.imp.sounds {
+                sounds.handle_first_input();
+            }
             self.got_first_input = true;
         }
 
-        if self.keyword_entry.handle_key(key, self.imp.painter.entered_keywords()) {
-            self.handle_command();
-            self.imp.painter.entered_keywords().clear();
+        let keywords = self.keywords();
+        let targets = self.target_keywords();
+        let ctx = KeyContext {
+            keywords: &keywords,
+            targets: &targets,
+            auto_submit: self.imp.config.game.difficulty().auto_submit,
+            alphabet: self.imp.config.game.alphabet(),
+        };
+        let ret = self.keyword_entry.handle_key(
+            key,
+            &ctx,
+            self.imp.painter.entered_keywords(),
+            &mut self.stats,
+            &self.imp.config.game.keyword_entry);
+
+        if ret.typo {
+            self.imp.emit(GameEvent::Typo);
+            self.dispatch_events();
+        }
+
+        if ret.command_entered {
+            self.handle_command(ret.auto_submitted);
+
+            // An auto-submit may have kept the key that started the next word.  The command may
+            // have changed what's on screen, so it's split using the new keywords.
+            let keywords = self.keywords();
+            let targets = self.target_keywords();
+            let ctx = KeyContext {
+                keywords: &keywords,
+                targets: &targets,
+                auto_submit: self.imp.config.game.difficulty().auto_submit,
+                alphabet: self.imp.config.game.alphabet(),
+            };
+            let tokens = self.keyword_entry.typed_tokens(&ctx);
+            self.imp.painter.entered_keywords().splice(.., tokens);
+        }
+    }
+
+    // Return a snapshot of the progress made so far
+    fn save(&self) -> SaveGame {
+        SaveGame {
+            version: SAVE_VERSION,
+            difficulty: Some(self.imp.config.game.difficulty),
+            money: self.imp.get_money(),
+            max_money: self.imp.config.game.money.max_money,
+            reputation: Some(self.imp.get_reputation()),
+            store_tiers: self.store.tiers(),
+            ingredients: self.imp.config.game.ingredient_area.ingredients.clone(),
+            cookers_unlocked: self.imp.config.ui.preparation_area.cookers.iter().map(|c| c.num_unlocked).collect(),
+            lifetime_stats: self.stats.lifetime().clone(),
+            key_stats: self.stats.keys().clone(),
         }
     }
 
+    // Restore the progress from the specified 'save'.  This must be done before the game starts,
+    // so the save is part of its recorded starting state.
+    fn load(&mut self, save: &SaveGame) -> Result<(), String> {
+        if !self.recording.is_empty() {
+            return Err("Saves can only be loaded before the game starts".to_string());
+        }
+
+        save.check_version()?;
+
+        // Each difficulty has its own words, so progress doesn't carry over between them
+        if let Some(difficulty) = save.difficulty {
+            if difficulty != self.imp.config.game.difficulty {
+                return Err(format!("Save is for difficulty {}, but the game is at difficulty {}", difficulty, self.imp.config.game.difficulty));
+            }
+        }
+
+        let mut cfg = self.imp.config.clone();
+        cfg.game.money.max_money = save.max_money;
+        cfg.game.ingredient_area.ingredients = save.ingredients.clone();
+        for (cooker, num_unlocked) in cfg.ui.preparation_area.cookers.iter_mut().zip(save.cookers_unlocked.iter()) {
+            cooker.num_unlocked = *num_unlocked;
+        }
+
+        self.update_config(&cfg);
+        self.update_recipes();
+
+        self.store.set_tiers(&save.store_tiers, &self.imp.config.ui.store);
+        *self.imp.cur_money.borrow_mut() = save.money.min(save.max_money);
+        if let Some(reputation) = save.reputation {
+            *self.imp.cur_reputation.borrow_mut() = reputation.clamp(0.0, self.imp.config.game.reputation.max);
+        }
+        self.stats.set_lifetime(&save.lifetime_stats);
+        self.stats.set_keys(&save.key_stats);
+        self.update_word_bias();
+
+        self.recording.save = Some(save.clone());
+
+        Ok(())
+    }
+
+    // Start playing back the specified 'recording'.  This must be a new game, created with the
+    // recording's config and seed.
+    fn start_replay(&mut self, recording: Recording) -> Result<(), String> {
+        if !self.recording.is_empty() {
+            return Err("Replays must start from a new game".to_string());
+        }
+
+        if recording.seed != self.recording.seed {
+            return Err(format!("Replay seed {} doesn't match game seed {}", recording.seed, self.recording.seed));
+        }
+
+        // Compare as JSON, the way configs are edited and stored
+        let replay_cfg = serde_json::to_value(&recording.config).map_err(|e| e.to_string())?;
+        let game_cfg = serde_json::to_value(&self.recording.config).map_err(|e| e.to_string())?;
+        if replay_cfg != game_cfg {
+            return Err("Replay config doesn't match the game's config".to_string());
+        }
+
+        if let Some(save) = &recording.save {
+            self.load(save)?;
+        }
+
+        self.replay = Some(Replay::new(recording));
+
+        Ok(())
+    }
+
+    // Handle the config being edited by the user.  Ignored while replaying.
+    fn report_config(&mut self, cfg: &OuterConfig) {
+        if self.replay.is_none() {
+            self.change_config(cfg);
+        }
+    }
+
+    fn change_config(&mut self, cfg: &OuterConfig) {
+        self.recording.record_config(cfg);
+        self.update_config(cfg);
+    }
+
     fn update_config(&mut self, cfg: &OuterConfig) {
         self.imp.config = cfg.clone();
-        self.imp.painter.update_config(&cfg.ui.images);
-        self.order_bar.update_config(&cfg.ui.order_bar, &cfg.game.order_bar, &self.imp);
+        self.imp.painter.update_config(&cfg.ui.images, cfg.game.alphabet());
+        self.order_bar.update_config(&cfg.ui.order_bar, &self.imp);
         self.ingredient_area.update_config(&self.imp, &self.imp.config.ui.ingredient_area, &self.imp.config.game.ingredient_area);
         self.preparation_area.update_config(&self.imp, &self.imp.config.ui.preparation_area);
         self.state_area.update_config(&self.imp.config.ui.state, &self.imp.config.game.state);
         self.keyword_entry.update_config(&self.imp.config.ui.keyword_entry);
+        self.streak_indicator.update_config(&self.imp.config.ui.streak);
+    }
+
+}
+
+///////// Config validation
+
+// Log any warnings about the specified 'cfg', and return an error describing any problems that
+// would stop the game from running
+fn check_config(cfg: &OuterConfig) -> Result<(), String> {
+    let issues = validation::validate_config(cfg);
+
+    for issue in issues.iter().filter(|issue| issue.severity == Severity::Warning) {
+        log(&format!("Config warning at {}: {}", issue.path, issue.message));
+    }
+
+    if validation::has_errors(&issues) {
+        return Err(format!("Invalid config:\n{}", validation::describe_errors(&issues)));
     }
 
+    Ok(())
 }
 
-static mut S_STATE: Option<Rc<RefCell<GameState>>> = None;
+// Convert the specified 'value' for JS with maps as plain objects, so it survives 'JSON.stringify'
+fn to_js_value<T: Serialize>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+}
 
+/// Check the specified 'config' without starting a game, returning a list of errors and warnings,
+/// each with the JSON path of the value it's about
 #[wasm_bindgen]
-pub fn init_state(config: JsValue, canvas: JsValue, images: JsValue, audio_ctx: JsValue, sounds: JsValue, words_db: JsValue, bad_words_db: JsValue) {
-    set_panic_hook();
+pub fn validate_config(config: JsValue) -> Result<JsValue, JsError> {
+    let issues = match serde_wasm_bindgen::from_value::<OuterConfig>(config) {
+        Ok(cfg) => validation::validate_config(&cfg),
+        Err(e) => vec![ConfigIssue::parse_error(format!("Failed parsing config: {}", e))],
+    };
 
-    let game_config;
-    match serde_wasm_bindgen::from_value::<OuterConfig>(config) {
-        Ok(cfg) => {
-            game_config = cfg;
-        }
-        Err(e) => {
-            log(&format!("Failed parsing config: {}", e));
-            return;
-        }
+    to_js_value(&issues)
+        .map_err(|e| JsError::new(&format!("Failed reporting config issues: {}", e)))
+}
+
+///////// Game
+
+/// A single game.  From JS it's created with a canvas and the loaded resources, and driven with
+/// 'run_frame' and 'report_keypress'.  Natively it runs headless: nothing is drawn to a screen or
+/// played, and time only advances when 'step' is called.
+#[wasm_bindgen]
+pub struct Game {
+    state: GameState,
+}
+
+#[wasm_bindgen]
+impl Game {
+    /// Start a new game drawing to the specified 'canvas'.  If 'seed' isn't specified a random one
+    /// is picked.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_canvas(config: JsValue, canvas: JsValue, images: JsValue, audio_ctx: JsValue, sounds: JsValue, word_lists: JsValue, bad_words_db: JsValue, seed: Option<u32>) -> Result<Game, JsError> {
+        set_panic_hook();
+
+        let game_config = serde_wasm_bindgen::from_value::<OuterConfig>(config)
+            .map_err(|e| JsError::new(&format!("Failed parsing config: {}", e)))?;
+        check_config(&game_config).map_err(|e| JsError::new(&e))?;
+
+        let seed = seed.unwrap_or_else(random_seed);
+
+        let word_lists = serde_wasm_bindgen::from_value::<HashMap<String, String>>(word_lists)
+            .map_err(|_| JsError::new("wordLists must map list names to strings"))?;
+        let words = load_words(&game_config.game.word_sources, &word_lists).map_err(|e| JsError::new(&e))?;
+        let bad_words_db: String = bad_words_db.dyn_into::<JsString>()
+            .map_err(|_| JsError::new("badWordsDb must be a string"))?.into();
+        let words_bank = WordBank::new(&words, &bad_words_db, &game_config.game).map_err(|e| JsError::new(&e))?;
+
+        let screen_canvas = canvas.dyn_into::<HtmlCanvasElement>()
+            .map_err(|_| JsError::new("canvas must be an HTMLCanvasElement"))?;
+
+        let painter_images = Images::new(images, &game_config.ui.images);
+
+        let painter = Painter::new(Box::new(CanvasBackend::new(screen_canvas, painter_images)?), game_config.game.alphabet());
+
+        let sounds = Sounds::new(audio_ctx, sounds, &game_config.ui.sounds);
+
+        Ok(Game {
+            state: GameState::new(game_config, seed, painter, Some(sounds), words_bank),
+        })
     }
-    
-    let order_bar = OrderBar::new(&game_config.ui.order_bar, &game_config.game.order_bar);
 
-    let words_bank = WordBank::new(
-        &words_db.dyn_into::<JsString>().expect("wordsDb").into(),
-        &bad_words_db.dyn_into::<JsString>().expect("badWords").into(),
-        game_config.game.word_level as usize);
+    /// The seed the game was started with, which can be used to reproduce it
+    #[wasm_bindgen(getter)]
+    pub fn seed(&self) -> u32 {
+        self.state.recording.seed
+    }
 
-    let offscreen_canvas = OffscreenCanvas::new(2560, 1440).expect("offscreen canvas");
-    let offscreen_context = offscreen_canvas.get_context("2d").unwrap().unwrap()
-                        .dyn_into::<OffscreenCanvasRenderingContext2d>().unwrap();
+    /// Advance the game by the time since the previous frame, and draw it
+    pub fn run_frame(&mut self) {
+        let now = Instant::now();
+        self.state.frame_times.push((now, now));
 
-    let screen_canvas= canvas.dyn_into::<HtmlCanvasElement>().expect("canvas");
+        let frame_time = self.state.update_frame_times();
+        self.state.advance(frame_time);
+        self.state.draw();
 
-    let painter_images = Images::new(images, &game_config.ui.images);
+        self.state.frame_times.last_mut().unwrap().1 = Instant::now();
+    }
 
-    let painter = Painter::new(painter_images, offscreen_context);
+    pub fn report_keypress(&mut self, key: &str) {
+        self.state.report_key(key);
+    }
 
-    let sounds = Sounds::new(audio_ctx, sounds, &game_config.ui.sounds);
+    pub fn update_config(&mut self, config: JsValue) -> Result<(), JsError> {
+        let cfg = serde_wasm_bindgen::from_value::<OuterConfig>(config)
+            .map_err(|e| JsError::new(&format!("Failed parsing config: {}", e)))?;
+        check_config(&cfg).map_err(|e| JsError::new(&e))?;
 
-    let game_imp = GameImp {
-        cur_money: RefCell::new(game_config.game.money.starting_money),
-        words_bank: words_bank,
-        painter: painter,
-        sounds: sounds,
-        config: game_config,
-        elapsed_time: 0.0,
-    };
+        self.state.report_config(&cfg);
 
-    let preparation_area = PreparationArea::new(&game_imp, &game_imp.config.ui.preparation_area);
+        Ok(())
+    }
 
-    let ingredient_area = IngredientArea::new(&game_imp, &game_imp.config.ui.ingredient_area, &game_imp.config.game.ingredient_area);
+    /// Return a snapshot of the game's progress, to be passed to 'load_state' later
+    pub fn save_state(&self) -> Result<JsValue, JsError> {
+        to_js_value(&self.state.save())
+            .map_err(|e| JsError::new(&format!("Failed saving: {}", e)))
+    }
 
-    let store = UpgradeStore::new(&game_imp, &game_imp.config.ui.store);
+    /// Restore progress previously returned by 'save_state'.  This must be done before the first
+    /// frame.
+    pub fn load_state(&mut self, save: JsValue) -> Result<(), JsError> {
+        let save = serde_wasm_bindgen::from_value::<SaveGame>(save)
+            .map_err(|e| JsError::new(&format!("Failed parsing save: {}", e)))?;
 
-    let state_area = StateArea::new(&game_imp.config.ui.state, &game_imp.config.game.state, &game_imp);
+        self.state.load(&save).map_err(|e| JsError::new(&e))
+    }
 
-    let keyword_entry = KeywordEntry::new(&game_imp.config.ui.keyword_entry);
+    /// Return the recording of the game so far, to be passed to 'start_replay' later
+    pub fn get_recording(&self) -> Result<JsValue, JsError> {
+        to_js_value(&self.state.recording)
+            .map_err(|e| JsError::new(&format!("Failed saving recording: {}", e)))
+    }
 
-    let mut state = GameState{
-        screen_canvas: screen_canvas,
-        offscreen_canvas: offscreen_canvas,
-        order_bar: order_bar,
-        ingredient_area: ingredient_area,
-        preparation_area: preparation_area,
-        store: store,
-        keyword_entry: keyword_entry,
-        got_first_input: false,
-        state_area: state_area,
-        frame_times: Vec::new(),
-        imp: game_imp,
-        fps_str: "".to_string(),
-    };
+    /// Play back a recording returned by 'get_recording'.  The game must be new, and created with
+    /// the recording's config and seed.
+    pub fn start_replay(&mut self, recording: JsValue) -> Result<(), JsError> {
+        let recording = serde_wasm_bindgen::from_value::<Recording>(recording)
+            .map_err(|e| JsError::new(&format!("Failed parsing recording: {}", e)))?;
 
-    state.frame_times.push((Instant::now(), Instant::now()));
+        self.state.start_replay(recording).map_err(|e| JsError::new(&e))
+    }
 
-    state.update_recipes();
+    /// Return the events that happened since the last call, oldest first
+    pub fn drain_events(&mut self) -> Result<JsValue, JsError> {
+        let events: Vec<GameEvent> = self.state.pending_events.drain(..).collect();
+        to_js_value(&events)
+            .map_err(|e| JsError::new(&format!("Failed reporting events: {}", e)))
+    }
 
-    if state.imp.config.game.unlock_all {
-        let mut upgrades: Vec<StoreUpgradeConfig> = Vec::new();
-        state.store.unlock_all(&mut upgrades, &state.imp.config.ui.store);
-        state.process_store_upgrades(&upgrades);
+    /// Return typing and restaurant stats for the current day, previous days, and overall
+    pub fn get_stats(&self) -> Result<JsValue, JsError> {
+        to_js_value(&self.state.stats.report())
+            .map_err(|e| JsError::new(&format!("Failed reporting stats: {}", e)))
     }
 
-    unsafe {
-        S_STATE = Some(Rc::new(RefCell::new(state)));
+    /// Stop the game.  Its canvas, images and sounds are released, and it can't be used afterwards.
+    pub fn teardown(self) {
     }
-    
 }
 
-fn run_frame_imp(state_rc: &Rc<RefCell<GameState>>) {
-    let mut state = state_rc.borrow_mut();
+impl Game {
+    /// Create a headless game using the specified 'config'.  'word_lists' holds the text of each of
+    /// the config's 'word_sources' by name.  The same 'seed' and inputs always produce the same
+    /// game.  Return an error if the config has problems that would stop the game from running.
+    pub fn new(config: OuterConfig, seed: u32, word_lists: &HashMap<String, String>, bad_words_db: &String) -> Result<Self, String> {
+        check_config(&config)?;
 
-    let now = Instant::now();
-    state.frame_times.push((now, now));
+        let words = load_words(&config.game.word_sources, word_lists)?;
+        let words_bank = WordBank::new(&words, bad_words_db, &config.game)?;
+        let painter = Painter::new(Box::new(HeadlessBackend::new(&config.ui.images)), config.game.alphabet());
 
-    state.think();
-    state.draw();
+        Ok(Game {
+            state: GameState::new(config, seed, painter, None, words_bank),
+        })
+    }
 
-    state.frame_times.last_mut().unwrap().1 = Instant::now();
-}
+    /// Create a headless game that plays back the specified 'recording', which must have been
+    /// made using the same word lists
+    pub fn from_recording(recording: Recording, word_lists: &HashMap<String, String>, bad_words_db: &String) -> Result<Self, String> {
+        let mut game = Game::new(recording.config.clone(), recording.seed, word_lists, bad_words_db)?;
+        game.state.start_replay(recording)?;
 
-#[wasm_bindgen]
-pub fn run_frame() {
-    unsafe {
-        #[allow(static_mut_refs)]
-        let state: &Rc<RefCell<GameState>> = S_STATE.as_mut().unwrap();
-        run_frame_imp(state);
+        Ok(game)
     }
-}
 
+    /// Play back the specified 'recording'.  The game must be new, and created with the
+    /// recording's config and seed.
+    pub fn play_recording(&mut self, recording: Recording) -> Result<(), String> {
+        self.state.start_replay(recording)
+    }
 
-#[wasm_bindgen]
-pub fn report_keypress(key: &str) {
-    unsafe {
-        #[allow(static_mut_refs)]
-        let state: &Rc<RefCell<GameState>> = S_STATE.as_mut().unwrap();
-        state.borrow_mut().handle_key(key, state);
+    /// Advance the simulation by 'frame_time' seconds, as if a frame took that long.  While
+    /// replaying, the recorded frame time is used instead.
+    pub fn step(&mut self, frame_time: f64) {
+        self.state.advance(frame_time);
     }
-}
 
-#[wasm_bindgen]
-pub fn update_config(config: JsValue) {
-    match serde_wasm_bindgen::from_value::<OuterConfig>(config) {
-        Ok(cfg) => {
-            unsafe {
-                #[allow(static_mut_refs)]
-                let state: &Rc<RefCell<GameState>> = S_STATE.as_mut().unwrap();
-                state.borrow_mut().update_config(&cfg);
-            }
-        }
-        Err(e) => {
-            log(&format!("Failed parsing config: {}", e));
+    /// Run the drawing code for the current state, without drawing anything
+    pub fn draw(&self) {
+        self.state.draw();
+    }
+
+    /// Handle the specified 'key' being pressed, using the same names as JS 'KeyboardEvent.key'
+    pub fn handle_key(&mut self, key: &str) {
+        self.state.report_key(key);
+    }
+
+    /// Type each character of the specified 'text', and then press Enter
+    pub fn enter_command(&mut self, text: &str) {
+        for c in text.chars() {
+            self.state.report_key(&c.to_string());
         }
+        self.state.report_key("Enter");
+    }
+
+    /// Return whether a recording is still being played back
+    pub fn is_replaying(&self) -> bool {
+        self.state.replay.is_some()
+    }
+
+    /// Return the recording of everything that happened in this game so far
+    pub fn recording(&self) -> &Recording {
+        &self.state.recording
+    }
+
+    /// Return the keywords currently on screen
+    pub fn keywords(&self) -> Vec<String> {
+        self.state.keywords().iter().map(|kw| kw.to_string()).collect()
+    }
+
+    pub fn money(&self) -> i32 {
+        self.state.imp.get_money()
+    }
+
+    pub fn in_store(&self) -> bool {
+        self.state.state_area.in_store()
+    }
+
+    pub fn config(&self) -> &OuterConfig {
+        &self.state.imp.config
+    }
+
+    /// Switch to the specified 'config', as if it were edited while playing.  Return an error if
+    /// it has problems that would stop the game from running.
+    pub fn change_config(&mut self, config: &OuterConfig) -> Result<(), String> {
+        check_config(config)?;
+        self.state.report_config(config);
+
+        Ok(())
+    }
+
+    /// Return the events that happened since the last call, oldest first
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        self.state.pending_events.drain(..).collect()
+    }
+
+    /// Return typing and restaurant stats for the current day, previous days, and overall
+    pub fn stats(&self) -> StatsReport {
+        self.state.stats.report()
+    }
+
+    /// Return a snapshot of the progress made so far
+    pub fn save(&self) -> SaveGame {
+        self.state.save()
+    }
+
+    /// Restore the progress from the specified 'save'.  This must be done before the first step.
+    pub fn load(&mut self, save: &SaveGame) -> Result<(), String> {
+        self.state.load(save)
     }
 }
\ No newline at end of file
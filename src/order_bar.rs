@@ -1,23 +1,20 @@
 
+use crate::events::GameEvent;
 use crate::ingredients::{IngredientStack, MovableIngredient};
 use crate::painter::{BackgroundConfig, ProgressBarConfig, TextConfig};
+use crate::reputation::ReputationGameConfig;
+use crate::streak::StreakGameConfig;
 use crate::traits::{BaseGame, Image, Sound};
+use crate::word_source::WordPool;
 
 use engine_p::interpolable::{Interpolable, Pos2d};
 use engine_p::sounds::PlaybackConfig;
 use serde::{Serialize,Deserialize};
-use wasm_bindgen::prelude::*;
 
 use std::collections::HashSet;
 use std::rc::Rc;
 use std::usize::MAX;
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
-
 #[derive(Serialize, Deserialize, Clone)]
 pub struct OrderIngredientConfig {
     pub ing: Image,
@@ -30,6 +27,10 @@ pub struct OrderConfig {
     pub ings: Vec<OrderIngredientConfig>,
     pub weight: f64, // how likely this order is to be chosen
     pub depreciation_seconds: f64, // seconds until order price is reduced
+    pub patience_seconds: f64, // seconds until the customer gives up and leaves
+    pub leave_penalty: i32, // money lost when the customer leaves
+    pub reputation_weight: f64, // added to 'weight' per point of reputation
+    pub strict_order: bool, // ingredients must arrive bottom to top, at difficulties with 'strict_assembly'
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -39,8 +40,12 @@ pub struct OrderBarUiConfig {
     pub bg: BackgroundConfig,
     pub text_price: TextConfig,
     pub text_keyword: TextConfig,
-    pub text_remaining: TextConfig,
-    pub progress_bar: ProgressBarConfig,
+    pub text_remaining: TextConfig, // how many customers are waiting for a free slot
+    pub text_rejected: TextConfig,  // replaces the price for a while after an out-of-order delivery
+    pub reject_message: String, // shown by text_rejected
+    pub reject_seconds: f64,
+    pub progress_bar: ProgressBarConfig, // shows the customer's remaining patience
+    pub leave_offset: Pos2d, // where orders move to, relative to where they are, when the customer leaves
     pub money_sound: PlaybackConfig<Sound>,
     pub orders: Vec<OrderConfig>,
 }
@@ -48,15 +53,19 @@ pub struct OrderBarUiConfig {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct OrderBarGameConfig {
     pub order_period: f64,
+    pub max_visible_orders: usize, // orders shown in the bar at once
+    pub max_waiting: usize,        // customers that queue up for a slot in the bar, beyond those
 }
 
 #[derive(PartialEq)]
 enum OrderBarStackState {
     Normal,
     Serving,
+    Leaving, // the customer ran out of patience
 }
 struct OrderBarStackThinkRet {
     start_serving: bool,
+    start_leaving: bool,
     pos_done: bool,
 }
 
@@ -64,13 +73,22 @@ struct OrderBarStack {
     stack: IngredientStack,
     price: i32,
     state: OrderBarStackState,
+    age: f64, // seconds since the order was created
+    arrived: bool, // whether the order has slid into its place in the bar
+    depreciation_seconds: f64,
+    depreciation_time: f64, // seconds since the price was last reduced
+    leave_penalty: i32,
+    strict_order: bool,
+    reject_time: f64, // seconds left showing that a delivery was rejected
 }
 
 impl OrderBarStack {
-    fn new(pos: Interpolable<Pos2d>, keyword: &Rc<String>, price: i32, depreciation_seconds: f64) -> Self {
+    fn new(pos: Interpolable<Pos2d>, keyword: &Rc<String>, price: i32, cfg: &OrderConfig) -> Self {
         let mut stack = IngredientStack::new(pos);
 
-        stack.progress = Some(Interpolable::new(0.0, 1.0/depreciation_seconds));
+        // The progress bar counts down the customer's patience, once the order is in place
+        let progress = Interpolable::new(1.0, 1.0/cfg.patience_seconds);
+        stack.progress = Some(progress);
         stack.text = Some(Rc::new(format!("$ {}", price)));
         stack.sub_text = Some(keyword.clone());
 
@@ -78,30 +96,53 @@ impl OrderBarStack {
             stack: stack,
             price: price,
             state: OrderBarStackState::Normal,
+            age: 0.0,
+            arrived: false,
+            depreciation_seconds: cfg.depreciation_seconds,
+            depreciation_time: 0.0,
+            leave_penalty: cfg.leave_penalty,
+            strict_order: cfg.strict_order,
+            reject_time: 0.0,
         }
     }
 
-    fn think(&mut self, game: &dyn BaseGame) -> OrderBarStackThinkRet {
+    fn think(&mut self, game: &dyn BaseGame, cfg_ui: &OrderBarUiConfig) -> OrderBarStackThinkRet {
         let mut ret = OrderBarStackThinkRet {
             start_serving: false,
+            start_leaving: false,
             pos_done: false,
         };
 
+        self.age += game.elapsed_time();
+
+        if self.reject_time > 0.0 {
+            self.reject_time -= game.elapsed_time();
+            if self.reject_time <= 0.0 {
+                self.set_price(self.price);
+            }
+        }
+
         let stack_ret = self.stack.think(game);
 
-        if stack_ret.pos_done {
-            if let Some(inner_progress) = &self.stack.progress {
-                if self.price > 1 && self.state == OrderBarStackState::Normal {
-                    inner_progress.set_cur(1.0);
-                }
+        if self.state != OrderBarStackState::Normal {
+            ret.pos_done = stack_ret.pos_done;
+            return ret;
+        }
+
+        // Customers only start waiting once their order is in place
+        if !self.arrived && stack_ret.pos_done {
+            self.arrived = true;
+            if let Some(progress) = &self.stack.progress {
+                progress.set_end(0.0);
             }
         }
 
-        if stack_ret.progress_done {
-            self.set_price(self.price-1);
-            if self.price > 1 {
-                if let Some(inner_progress) = &self.stack.progress {
-                    inner_progress.set_cur(1.0);
+        if self.arrived {
+            self.depreciation_time += game.elapsed_time();
+            if self.depreciation_time >= self.depreciation_seconds {
+                self.depreciation_time -= self.depreciation_seconds;
+                if self.price > 1 {
+                    self.set_price(self.price-1);
                 }
             }
         }
@@ -112,6 +153,15 @@ impl OrderBarStack {
             self.stack.pos.set_end(self.stack.pos.own_cur() + Pos2d::new(0.0, -300.0));
             ret.start_serving = true;
         }
+        else if stack_ret.progress_done {
+            // The customer leaves, taking anything already delivered with them
+            self.state = OrderBarStackState::Leaving;
+            self.stack.progress = None;
+            self.stack.sub_text = None;
+            self.stack.text = Some(Rc::new(format!("-$ {}", self.leave_penalty)));
+            self.stack.pos.set_end(self.stack.pos.own_cur() + cfg_ui.leave_offset);
+            ret.start_leaving = true;
+        }
 
         ret.pos_done = stack_ret.pos_done;
 
@@ -120,8 +170,16 @@ impl OrderBarStack {
 
     fn set_price(&mut self, price: i32) {
         self.price = price;
-        self.stack.text = Some(Rc::new(format!("$ {}", self.price)));
-    } 
+        if self.reject_time <= 0.0 {
+            self.stack.text = Some(Rc::new(format!("$ {}", self.price)));
+        }
+    }
+
+    // Show that ingredients were delivered out of order
+    fn reject(&mut self, cfg_ui: &OrderBarUiConfig) {
+        self.reject_time = cfg_ui.reject_seconds;
+        self.stack.text = Some(Rc::new(cfg_ui.reject_message.clone()));
+    }
 }
 
 // ==========================
@@ -132,6 +190,7 @@ impl OrderBarStack {
 pub struct OrderBar {
     orders: Vec<OrderBarStack>,
     //orders_remaining: i32,
+    waiting: usize, // customers waiting for a slot in the bar
     pos: Interpolable<Pos2d>,
     new_item_timer: Interpolable<f64>,
     available_ings: HashSet<Image>,
@@ -139,13 +198,14 @@ pub struct OrderBar {
 
 impl OrderBar {
     /// Create a new OrderBar
-    pub fn new(cfg_ui: &OrderBarUiConfig, cfg_game: &OrderBarGameConfig) -> Self {
+    pub fn new(cfg_ui: &OrderBarUiConfig, cfg_game: &OrderBarGameConfig, cfg_rep: &ReputationGameConfig) -> Self {
         let new_item_timer = Interpolable::new(0.0, 1.0);
-        new_item_timer.set_end(cfg_game.order_period);
+        new_item_timer.set_end(cfg_game.order_period * cfg_rep.order_period_scale(cfg_rep.starting));
 
         OrderBar {
             orders: Vec::new(),
             //orders_remaining: 10,
+            waiting: 0,
             pos: Interpolable::new(cfg_ui.pos, 1000.0),
             new_item_timer: new_item_timer,
             available_ings: HashSet::new(),
@@ -153,15 +213,23 @@ impl OrderBar {
     }
 
     /// Reset the state of the OrderBar, to start a new day
-    pub fn reset_state(&mut self) {
+    pub fn reset_state(&mut self, game: &dyn BaseGame, cfg_game: &OrderBarGameConfig, cfg_rep: &ReputationGameConfig) {
         self.orders.clear();
-        self.new_item_timer.set_cur(0.0);
+        self.waiting = 0;
+        self.start_timer(game, cfg_game, cfg_rep);
     }
 
     /// Update the state of the OrderBar for the frame
-    pub fn think(&mut self, game: &dyn BaseGame, cfg_ui: &OrderBarUiConfig, cfg_game: &OrderBarGameConfig) {
+    pub fn think(&mut self, game: &dyn BaseGame, cfg_ui: &OrderBarUiConfig, cfg_game: &OrderBarGameConfig, cfg_rep: &ReputationGameConfig, cfg_streak: &StreakGameConfig) {
         if self.new_item_timer.advance(game.elapsed_time()) {
-            self.create_order(game, cfg_ui, cfg_game);
+            // If nothing can be made yet, the customer goes elsewhere and another comes next period
+            if self.orders.len() < cfg_game.max_visible_orders {
+                self.create_order(game, cfg_ui);
+            }
+            else if self.waiting < cfg_game.max_waiting {
+                self.waiting += 1;
+            }
+            self.restart_timer(game, cfg_game, cfg_rep);
         }
 
         self.pos.advance(game.elapsed_time());
@@ -172,12 +240,20 @@ impl OrderBar {
 
             {
                 let order = &mut self.orders[order_idx];
-                think_ret = order.think(game);
+                think_ret = order.think(game, cfg_ui);
+
+                if think_ret.start_leaving {
+                    game.emit(GameEvent::OrderExpired { penalty: order.leave_penalty, wait_time: order.age });
+                }
 
                 if think_ret.pos_done {
                     if order.state == OrderBarStackState::Serving {
-                        game.add_money(self.orders[order_idx].price);
-                        game.sounds().play_sound(&cfg_ui.money_sound);
+                        let tip = cfg_rep.tip(order.price, game.get_reputation());
+                        let streak_bonus = cfg_streak.bonus(order.price, game.get_streak());
+                        game.emit(GameEvent::OrderServed { price: order.price, tip: tip, streak_bonus: streak_bonus, serve_time: order.age });
+                        served_idx = order_idx;
+                    }
+                    else if order.state == OrderBarStackState::Leaving {
                         served_idx = order_idx;
                     }
                 }
@@ -186,28 +262,97 @@ impl OrderBar {
 
         if served_idx != MAX {
             self.orders.remove(served_idx);
+            self.layout_orders(game, cfg_ui);
 
-            // Update positions of all our orders
-            let mut xpos = cfg_ui.order_margin;
-            for ord in self.orders.iter_mut() {
-                ord.stack.pos.set_end((xpos, 0).into());
-                xpos += ord.stack.width(game) + cfg_ui.order_margin;
+            // The next customer in line takes the free slot, while the one after them keeps coming.
+            // A stopped timer was waiting for room in the line, which there is now.
+            if self.waiting > 0 {
+                if self.create_order(game, cfg_ui) {
+                    self.waiting -= 1;
+                }
+                if !self.new_item_timer.is_moving() {
+                    self.restart_timer(game, cfg_game, cfg_rep);
+                }
             }
-            
-            if self.orders.len() < 5 {
-                self.new_item_timer.set_cur(0.0);
+            else {
+                self.restart_timer(game, cfg_game, cfg_rep);
             }
         }
     }
 
-    /// Handle the user typing the specified 'keywords' on the command line
-    pub fn handle_command(&mut self, keywords: &Vec<String>, selected_ings: &mut Vec<MovableIngredient>, _game:&dyn BaseGame) -> bool {
+    /// Give up on all the orders still waiting, since the day is over.  Orders already being served
+    /// were delivered, so they're paid for.
+    pub fn miss_orders(&mut self, game: &dyn BaseGame, cfg_rep: &ReputationGameConfig, cfg_streak: &StreakGameConfig) {
+        for order in self.orders.drain(..) {
+            match order.state {
+                OrderBarStackState::Normal => game.emit(GameEvent::OrderMissed),
+                OrderBarStackState::Serving => {
+                    let tip = cfg_rep.tip(order.price, game.get_reputation());
+                    let streak_bonus = cfg_streak.bonus(order.price, game.get_streak());
+                    game.emit(GameEvent::OrderServed { price: order.price, tip: tip, streak_bonus: streak_bonus, serve_time: order.age });
+                }
+                OrderBarStackState::Leaving => {}
+            }
+        }
+
+        for _ in 0..std::mem::take(&mut self.waiting) {
+            game.emit(GameEvent::OrderMissed);
+        }
+    }
+
+    // Start waiting for the next customer, if there's room for them in the bar or the line
+    fn restart_timer(&self, game: &dyn BaseGame, cfg_game: &OrderBarGameConfig, cfg_rep: &ReputationGameConfig) {
+        if self.orders.len() < cfg_game.max_visible_orders || self.waiting < cfg_game.max_waiting {
+            self.start_timer(game, cfg_game, cfg_rep);
+        }
+    }
+
+    // Start timing the next customer's arrival.  A better reputation brings customers in more
+    // often, at the reputation they arrive with.
+    fn start_timer(&self, game: &dyn BaseGame, cfg_game: &OrderBarGameConfig, cfg_rep: &ReputationGameConfig) {
+        self.new_item_timer.set_cur(0.0);
+        self.new_item_timer.set_end(cfg_game.order_period * cfg_rep.order_period_scale(game.get_reputation()));
+    }
+
+    // Move our orders to their places in the bar, left to right.  If they're too wide to fit in
+    // the background, they're squeezed closer together, overlapping if needed.  Orders that are
+    // being served or leaving are left alone.
+    fn layout_orders(&self, game: &dyn BaseGame, cfg_ui: &OrderBarUiConfig) {
+        let orders: Vec<&OrderBarStack> = self.orders.iter()
+            .filter(|o| o.state == OrderBarStackState::Normal)
+            .collect();
+        let widths: Vec<f64> = orders.iter().map(|o| o.stack.width(game)).collect();
+        let last_width = widths.last().copied().unwrap_or(0.0);
+
+        // Space from the left of the first order to the left of the last one
+        let needed: f64 = widths.iter().map(|w| w + cfg_ui.order_margin).sum::<f64>() - last_width - cfg_ui.order_margin;
+        let available = cfg_ui.bg.offset.x + cfg_ui.bg.width - 2.0 * cfg_ui.order_margin - last_width;
+        let squeeze = if needed > available && needed > 0.0 { (available / needed).max(0.0) } else { 1.0 };
+
+        let mut xpos = cfg_ui.order_margin;
+        for (order, width) in orders.iter().zip(widths.iter()) {
+            order.stack.pos.set_end((xpos, 0).into());
+            xpos += (width + cfg_ui.order_margin) * squeeze;
+        }
+    }
+
+    /// Handle the user typing the specified 'keywords' on the command line.  If 'strict_assembly'
+    /// is set, orders with 'strict_order' only take ingredients bottom to top.
+    pub fn handle_command(&mut self, keywords: &Vec<String>, selected_ings: &mut Vec<MovableIngredient>, strict_assembly: bool, game:&dyn BaseGame, cfg_ui: &OrderBarUiConfig) -> bool {
         for keyword in keywords.iter() {
             for my_order in self.orders.iter_mut() {
                 if let Some(stack_word) = &my_order.stack.sub_text {
                     if **stack_word == *keyword {
                         // Found a matching order.  Send all matching ingredients to it
-                        my_order.stack.try_ungray_ingredients(selected_ings);
+                        if strict_assembly && my_order.strict_order {
+                            if my_order.stack.try_ungray_ingredients_in_order(selected_ings) {
+                                my_order.reject(cfg_ui);
+                                game.emit(GameEvent::DeliveryRejected);
+                            }
+                        }
+                        else {
+                            my_order.stack.try_ungray_ingredients(selected_ings);
+                        }
                         return true;
                     }
                 }
@@ -223,14 +368,19 @@ impl OrderBar {
 
         for i in 0..self.orders.len() {
             let order = &self.orders[i];
-            order.stack.draw(game, Some(&cfg_ui.progress_bar), Some(&cfg_ui.text_price), Some(&cfg_ui.text_keyword));
+            let text_price = if order.reject_time > 0.0 { &cfg_ui.text_rejected } else { &cfg_ui.text_price };
+            order.stack.draw(game, Some(&cfg_ui.progress_bar), Some(text_price), Some(&cfg_ui.text_keyword));
         }
 
         //game.draw_text(&format!("Remaining: {}", self.orders_remaining), &self.pos.cur(), 1.0, &cfg.text_remaining);
+        if self.waiting > 0 {
+            game.painter().draw_text(&format!("Waiting: {}", self.waiting), &self.pos.cur(), cfg_ui.bg.width, &cfg_ui.text_remaining);
+        }
     }
 
-    /// Create a new order in the OrderBar
-    pub fn create_order(&mut self, game: &dyn BaseGame, cfg_ui: &OrderBarUiConfig, _cfg_game: &OrderBarGameConfig) {
+    /// Create a new order in the OrderBar, and return whether one could be made with the
+    /// available ingredients
+    pub fn create_order(&mut self, game: &dyn BaseGame, cfg_ui: &OrderBarUiConfig) -> bool {
         //if self.orders_remaining == 0 {
         //    return;
         //}
@@ -247,12 +397,18 @@ impl OrderBar {
             }
         }
 
-        let total_weight: f64 = orders.iter().map(|e| e.weight).sum();
-        let mut order_selector = js_sys::Math::random() * (total_weight as f64);
+        if orders.is_empty() {
+            return false;
+        }
+
+        // Some orders only get popular once the restaurant has a reputation
+        let weight = |order: &OrderConfig| (order.weight + order.reputation_weight * game.get_reputation()).max(0.0);
+        let total_weight: f64 = orders.iter().map(|e| weight(e)).sum();
+        let mut order_selector = game.rng().random() * (total_weight as f64);
         let mut order_to_make = &orders[0];
 
         for order in orders.iter() {
-            order_selector -= order.weight;
+            order_selector -= weight(order);
             if order_selector <= 0.0 {
                 order_to_make = order;
                 break;
@@ -264,22 +420,14 @@ impl OrderBar {
             Pos2d::new(1000.0, 0.0),
             1000.0,
             &self.pos),
-            &game.word_bank().get_new_word(),
+            &game.word_bank().get_new_word(WordPool::Orders, game.rng()),
         0,
-        order_to_make.depreciation_seconds);
-
-        let mut xpos = cfg_ui.order_margin;
-        for i in 0..self.orders.len() {
-            let order = &self.orders[i];
-            xpos += order.stack.width(game) + cfg_ui.order_margin;
-        }
+        order_to_make);
 
-        new_order.stack.pos.set_end((xpos, 0).into());
-        
         // Figure out the ingredients for 'order_to_make'
         let mut price: i32 = 0;
         for ing in order_to_make.ings.iter() {
-            let ing_chance = js_sys::Math::random();
+            let ing_chance = game.rng().random();
             if ing_chance > ing.chance || !self.available_ings.contains(&ing.ing) {
                 continue;
             }
@@ -293,10 +441,20 @@ impl OrderBar {
 
         new_order.set_price(price);
 
+        for keyword in new_order.stack.sub_text.iter() {
+            game.emit(GameEvent::OrderCreated { keyword: keyword.to_string() });
+        }
+
         self.orders.push(new_order);
+        self.layout_orders(game, cfg_ui);
 
-        if self.orders.len() < 5 {
-            self.new_item_timer.set_cur(0.0);
+        true
+    }
+
+    /// Append the keywords of all our orders to the specified 'keywords'
+    pub fn collect_keywords(&self, keywords: &mut Vec<Rc<String>>) {
+        for order in self.orders.iter() {
+            keywords.extend(order.stack.sub_text.iter().cloned());
         }
     }
 
@@ -305,16 +463,11 @@ impl OrderBar {
     }
 
     /// Update our configuration
-    pub fn update_config(&mut self, cfg_ui: &OrderBarUiConfig, cfg_game: &OrderBarGameConfig, game: &dyn BaseGame) {
+    pub fn update_config(&mut self, cfg_ui: &OrderBarUiConfig, game: &dyn BaseGame) {
         self.pos.set_end(cfg_ui.pos);
+        self.layout_orders(game, cfg_ui);
 
-        let mut xpos = cfg_ui.order_margin;
-        for i in 0..self.orders.len() {
-            let order = &self.orders[i];
-            order.stack.pos.set_end((xpos, 0).into());
-            xpos += order.stack.width(game) + cfg_ui.order_margin;
-        }
-
-        self.new_item_timer.set_end(cfg_game.order_period);
+        // A new order period takes effect when the timer next restarts, so the customer on their
+        // way still arrives on time
     }
 }
@@ -1,10 +1,10 @@
 
-use crate::traits::Image;
+use crate::alphabet::AlphabetGameConfig;
+use crate::traits::{DrawBackend, Image};
 
-use engine_p::images::{Images, ImagesConfig};
+use engine_p::images::ImagesConfig;
 use engine_p::interpolable::{Interpolable, Pos2d};
 use serde::{Serialize,Deserialize};
-use web_sys::OffscreenCanvasRenderingContext2d;
 
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -49,9 +49,9 @@ pub struct RingConfig {
 
 
 pub struct Painter {
-    canvas: OffscreenCanvasRenderingContext2d,
-    images: Images<Image>,
+    canvas: Box<dyn DrawBackend>,
     entered_keywords: Vec<String>,
+    alphabet: AlphabetGameConfig, // for matching 'entered_keywords' against drawn commands
     keyword_r: Interpolable<f64>,
     keyword_g: Interpolable<f64>,
     keyword_b: Interpolable<f64>,
@@ -59,11 +59,11 @@ pub struct Painter {
 
 impl Painter {
 
-    pub fn new(images: Images<Image>, canvas: OffscreenCanvasRenderingContext2d) -> Self {
+    pub fn new(canvas: Box<dyn DrawBackend>, alphabet: &AlphabetGameConfig) -> Self {
         Painter {
             canvas: canvas,
-            images: images,
             entered_keywords: Vec::new(),
+            alphabet: alphabet.clone(),
             keyword_r: Interpolable::new(72.0, 111.0),
             keyword_g: Interpolable::new(23.0, 79.0),
             keyword_b: Interpolable::new(219.0, 231.0),
@@ -93,40 +93,40 @@ impl Painter {
     }
 
     pub fn draw_image(&self, image: &Image, pos: &Pos2d) {
-        self.images.draw_image(&self.canvas, image, pos.x, pos.y);
+        self.canvas.draw_image(image, pos.x, pos.y, false);
     }
 
     pub fn draw_gray_image(&self, image: &Image, pos: &Pos2d) {
-        self.images.draw_gray_image(&self.canvas, image, pos.x, pos.y);
+        self.canvas.draw_image(image, pos.x, pos.y, true);
     }
 
     pub fn draw_area_background(&self, pos: &Pos2d, cfg: &BackgroundConfig) {
         let c = &self.canvas;
 
-        c.set_stroke_style_str(&cfg.border_style);
-        c.set_fill_style_str(&cfg.bg_style);
+        c.set_stroke_style(&cfg.border_style);
+        c.set_fill_style(&cfg.bg_style);
         c.set_line_width(cfg.border_width);
 
         // Draw backgound first
         c.set_global_alpha(cfg.bg_alpha);
         c.begin_path();
-        c.round_rect_with_f64(
+        c.round_rect(
             pos.x + cfg.offset.x,
             pos.y + cfg.offset.y,
             cfg.width,
             cfg.height,
-            cfg.corner_radius).expect("bg");
+            cfg.corner_radius);
         c.fill();
 
         // Draw border
         c.set_global_alpha(cfg.border_alpha);
         c.begin_path();
-        c.round_rect_with_f64(
+        c.round_rect(
             pos.x + cfg.offset.x,
             pos.y + cfg.offset.y,
             cfg.width,
             cfg.height,
-            cfg.corner_radius).expect("border");
+            cfg.corner_radius);
         c.stroke();
 
         c.set_global_alpha(1.0);
@@ -137,14 +137,14 @@ impl Painter {
 
         // Draw the progress indicator
         self.canvas.set_global_alpha(cfg.done_alpha);
-        self.canvas.set_fill_style_str(&cfg.done_style);
+        self.canvas.set_fill_style(&cfg.done_style);
         self.canvas.begin_path();
-        self.canvas.round_rect_with_f64(
+        self.canvas.round_rect(
             pos.x + cfg.bg.offset.x,
             pos.y + cfg.bg.offset.y,
             cfg.bg.width * pct,
             cfg.bg.height,
-            cfg.bg.corner_radius).expect("progress");
+            cfg.bg.corner_radius);
         self.canvas.fill();
 
         self.canvas.set_global_alpha(1.0);
@@ -156,17 +156,17 @@ impl Painter {
         let c = &self.canvas;
 
         c.begin_path();
-        c.arc(pos.x, pos.y, r1, rad1, rad2).expect("ring1");
-        c.arc_with_anticlockwise(pos.x, pos.y, r2, rad2, rad1, true).expect("ring2");
+        c.arc(pos.x, pos.y, r1, rad1, rad2, false);
+        c.arc(pos.x, pos.y, r2, rad2, rad1, true);
         c.close_path();
 
         if cfg.stroke {
-            c.set_stroke_style_str(&cfg.style);
+            c.set_stroke_style(&cfg.style);
             c.set_line_width(10.0);
             c.stroke();
         }
         else {
-            c.set_fill_style_str(&cfg.style);
+            c.set_fill_style(&cfg.style);
             c.fill();
         }
     }
@@ -193,8 +193,8 @@ impl Painter {
 
         self.canvas.set_global_alpha(cfg.alpha);
         self.canvas.set_text_baseline("top");
-        self.canvas.set_stroke_style_str(&cfg.style);
-        self.canvas.set_fill_style_str(&cfg.style);
+        self.canvas.set_stroke_style(&cfg.style);
+        self.canvas.set_fill_style(&cfg.style);
 
         let mut draw_pos = *pos;
         if cfg.center_and_fit {
@@ -205,7 +205,7 @@ impl Painter {
             while text_width > width {
                 font_size -= 1;
                 self.canvas.set_font(&format!("{}px {}", font_size, cfg.font));
-                text_width = self.canvas.measure_text(text).expect("measure text").width();
+                text_width = self.canvas.measure_text(text).width;
             }
 
             // Senter horizontally
@@ -219,59 +219,63 @@ impl Painter {
             draw_fn = Box::new(|text, xpos, ypos| {
                 
                 // Draw the text normally, and then draw again using the 'filled' style
-                self.canvas.fill_text(text, xpos, ypos).expect("text");
+                self.canvas.fill_text(text, xpos, ypos);
 
-                self.canvas.set_fill_style_str(cfg.filled_style.as_ref().unwrap());
+                self.canvas.set_fill_style(cfg.filled_style.as_ref().unwrap());
 
-                let text_dim = self.canvas.measure_text(text).expect("measure text");
+                let text_dim = self.canvas.measure_text(text);
                 self.canvas.save();
                 
-                let text_height = text_dim.actual_bounding_box_ascent() + text_dim.actual_bounding_box_descent();
+                let text_height = text_dim.ascent + text_dim.descent;
                 let clip_y = ypos + text_height*(1.0-filled_pct);
 
                 self.canvas.begin_path();
-                self.canvas.rect(xpos, clip_y, text_dim.width(), text_height);
+                self.canvas.rect(xpos, clip_y, text_dim.width, text_height);
                 self.canvas.clip();
 
-                self.canvas.fill_text(text, xpos, ypos).expect("text");
+                self.canvas.fill_text(text, xpos, ypos);
                 
                 self.canvas.restore();
             });
         }
         else if cfg.stroke { // Stroke
             draw_fn = Box::new(|text, xpos, ypos| {
-                self.canvas.stroke_text(text, xpos, ypos).expect("text");
+                self.canvas.stroke_text(text, xpos, ypos);
             });
         }
         else { // Fill
             draw_fn = Box::new(|text, xpos, ypos| {
-                self.canvas.fill_text(text, xpos, ypos).expect("text");
+                self.canvas.fill_text(text, xpos, ypos);
             });
         }
     
         let mut drawn = false;
         if cfg.is_command {
-            if self.entered_keywords.iter().find(|x| *x == text).is_some() {
-                self.canvas.set_fill_style_str(
+            if self.entered_keywords.iter().any(|x| self.alphabet.matches(x, text)) {
+                self.canvas.set_fill_style(
                     &format!("rgb({},{},{})", self.keyword_r.cur() as i32, self.keyword_g.cur() as i32, self.keyword_b.cur() as i32));
                 self.canvas.set_font(&format!("bold {}px {}", font_size, cfg.font));
                 draw_fn(text, draw_pos.x, draw_pos.y);
                 drawn = true;
             }
             else if !self.entered_keywords.is_empty() &&
-                    text.starts_with(self.entered_keywords.last().unwrap())
+                    self.alphabet.is_prefix(self.entered_keywords.last().unwrap(), text)
             {
-                let last_keyword = self.entered_keywords.last().unwrap();
+                // Split 'text' after the typed characters, which may not be the same bytes
+                let typed_chars = self.entered_keywords.last().unwrap().chars().count();
+                let split = text.char_indices().nth(typed_chars).map_or(text.len(), |(idx, _)| idx);
+                let (typed, rest) = text.split_at(split);
+
                 // Underline the matching part of the word
                 self.canvas.set_font(&format!("italic {}px {}", font_size, cfg.font));
-                draw_fn(last_keyword, draw_pos.x, draw_pos.y);
+                draw_fn(typed, draw_pos.x, draw_pos.y);
 
-                let underlined_width = self.canvas.measure_text(last_keyword).expect("measure text");
+                let underlined_width = self.canvas.measure_text(typed);
                 //let new_x = draw_pos.xpos + underlined_width.actual_bounding_box_left() + underlined_width.actual_bounding_box_right();
-                let new_x = draw_pos.x + underlined_width.width();
+                let new_x = draw_pos.x + underlined_width.width;
 
                 self.canvas.set_font(&format!("{}px {}", font_size, cfg.font));
-                draw_fn(&text[last_keyword.len()..], new_x, draw_pos.y);
+                draw_fn(rest, new_x, draw_pos.y);
                 drawn = true;
             }
         }
@@ -288,24 +292,50 @@ impl Painter {
         self.draw_text_imp(text, pos, width, 1.0, cfg);
     }
     
+    /// Draw a caret just after the specified 'text_before', as drawn by 'draw_text' at the
+    /// specified 'pos' with the specified 'cfg'.  'cfg' must not be 'center_and_fit'.
+    pub fn draw_caret(&self, text_before: &str, pos: &Pos2d, cfg: &TextConfig) {
+        self.canvas.set_font(&format!("{}px {}", cfg.size, cfg.font));
+        let x_off = self.canvas.measure_text(text_before).width;
+        let draw_pos = *pos + cfg.offset + (x_off, 0.0).into();
+
+        self.canvas.set_global_alpha(cfg.alpha);
+        self.canvas.set_fill_style(&cfg.style);
+        self.canvas.fill_rect(draw_pos.x, draw_pos.y, 3.0, cfg.size as f64);
+        self.canvas.set_global_alpha(1.0);
+    }
+
     pub fn draw_text_with_filled(&self, text: &str, pos: &Pos2d, width: f64, filled_pct: f64, cfg: &TextConfig) {
         self.draw_text_imp(text, pos, width, filled_pct, cfg);
     }
 
 
-    pub fn update_config(&mut self, cfg_ui_images: &ImagesConfig<Image>) {
-        self.images.update_config(cfg_ui_images);
+    /// Fill the whole screen with the specified 'style', erasing anything previously drawn
+    pub fn clear_screen(&self, style: &str) {
+        self.canvas.set_fill_style(style);
+        self.canvas.clear_rect(0.0, 0.0, 2560.0, 1440.0);
+        self.canvas.fill_rect(0.0, 0.0, 2560.0, 1440.0);
+    }
+
+    /// Show everything drawn this frame
+    pub fn present(&self) {
+        self.canvas.present();
+    }
+
+    pub fn update_config(&mut self, cfg_ui_images: &ImagesConfig<Image>, alphabet: &AlphabetGameConfig) {
+        self.canvas.update_images_config(cfg_ui_images);
+        self.alphabet = alphabet.clone();
     }
 
     pub fn entered_keywords(&mut self) -> &mut Vec<String>{
         &mut self.entered_keywords
     }
 
-    pub fn images<'a>(&'a self) -> &'a Images<Image> {
-        &self.images
+    pub fn image_width(&self, image: &Image) -> f64 {
+        self.canvas.image_width(image)
     }
 
-    pub fn canvas(&self) -> &OffscreenCanvasRenderingContext2d {
-        &self.canvas
+    pub fn image_height(&self, image: &Image) -> f64 {
+        self.canvas.image_height(image)
     }
 }
\ No newline at end of file
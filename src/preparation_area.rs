@@ -1,20 +1,17 @@
 
+use crate::events::GameEvent;
 use crate::ingredients::{IngredientStack, MovableIngredient};
 use crate::painter::{BackgroundConfig, ProgressBarConfig, TextConfig};
 use crate::traits::{BaseGame, Image, Sound};
+use crate::utils::log;
+use crate::word_source::WordPool;
 
 use engine_p::interpolable::{Interpolable, Pos2d};
 use engine_p::sounds::PlaybackConfig;
 use serde::{Serialize,Deserialize};
-use wasm_bindgen::prelude::*;
 
 use std::collections::HashSet;
-
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
-}
+use std::rc::Rc;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct CookingRecipe {
@@ -57,7 +54,7 @@ impl PreparationAreaStack {
             MovableIngredient::new(cfg.base_image, Interpolable::new((0,0).into(), 1000.0)),
             true, 
             game);
-        stack.text = Some(game.word_bank().get_new_word());
+        stack.text = Some(game.word_bank().get_new_word(WordPool::Cookers, game.rng()));
         stack.progress = Some(Interpolable::new(0.0, 1.0));
         
         PreparationAreaStack {
@@ -78,7 +75,7 @@ impl PreparationAreaStack {
         }
     }
 
-    fn think(&mut self, cfg: &CookerConfig, game: &dyn BaseGame) {
+    fn think(&mut self, cooker_idx: usize, cfg: &CookerConfig, game: &dyn BaseGame) {
         if !self.is_unlocked {
             return;
         }
@@ -97,7 +94,7 @@ impl PreparationAreaStack {
                 progress.set_end(0.0);
             }
 
-            game.sounds().play_sound(&cfg.done_cooking_sound);
+            game.emit(GameEvent::CookingDone { cooker_idx: cooker_idx, cooker: cfg.base_image });
         }
 
         if ret.ingredient_arrived {
@@ -105,11 +102,11 @@ impl PreparationAreaStack {
             for progress in self.stack.progress.iter() {
                 progress.set_end(1.0);
 
-                // Figure out how long to play the sound for
-                let mut snd_cfg = cfg.cooking_sound.clone();
-                snd_cfg.play_length = Some(1.0/progress.speed());
-
-                game.sounds().play_sound(&snd_cfg);
+                game.emit(GameEvent::CookingStarted {
+                    cooker_idx: cooker_idx,
+                    cooker: cfg.base_image,
+                    cook_time: 1.0/progress.speed(),
+                });
             }
         }
     }
@@ -172,7 +169,7 @@ impl PreparationAreaStack {
             }
         }
 
-        self.stack.text = Some(game.word_bank().get_new_word());
+        self.stack.text = Some(game.word_bank().get_new_word(WordPool::Cookers, game.rng()));
 
         if self.is_cooked {
             // Add our cooked ingredients to the selected_ings
@@ -269,10 +266,15 @@ impl PreparationArea {
             cookers.push(instances);
         }
 
-        PreparationArea {
+        let mut ret = PreparationArea {
             pos: pos.clone(),
             cookers: cookers,
-        }
+        };
+
+        // Unlock the cookers the config starts with
+        ret.update_config(game, cfg);
+
+        ret
     }
 
     /// Reset our state for the start of a new day
@@ -286,9 +288,9 @@ impl PreparationArea {
 
     /// Update our state for the current frame
     pub fn think(&mut self, cfg: &PreparationAreaConfig, game: &dyn BaseGame) {
-        for (cooker_type, cfg) in self.cookers.iter_mut().zip(cfg.cookers.iter()) {
+        for (cooker_idx, (cooker_type, cfg)) in self.cookers.iter_mut().zip(cfg.cookers.iter()).enumerate() {
             for inst in cooker_type.iter_mut() {
-                inst.think(cfg, game);
+                inst.think(cooker_idx, cfg, game);
             }
         }
 
@@ -358,6 +360,15 @@ impl PreparationArea {
         }
     }
 
+    /// Append the keywords of all our unlocked cookers to the specified 'keywords'
+    pub fn collect_keywords(&self, keywords: &mut Vec<Rc<String>>) {
+        for cooker_type in self.cookers.iter() {
+            for cooker in cooker_type.iter().filter(|c| c.is_unlocked) {
+                keywords.extend(cooker.stack.text.iter().cloned());
+            }
+        }
+    }
+
     // Figure out which ingredients are possible to produce using our cooking recipes, and append 
     // the corresponding outputs to the specified 'ings'.
     pub fn append_possible_ingredients(&self, ings: &mut HashSet<Image>, cfg: &PreparationAreaConfig) {
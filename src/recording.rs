@@ -0,0 +1,137 @@
+use crate::save_game::SaveGame;
+use crate::OuterConfig;
+
+use serde::{Serialize,Deserialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordedKey {
+    pub frame: usize, // number of frames that had run when the key was pressed
+    pub time: f64,    // simulation time when the key was pressed
+    pub key: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecordedConfig {
+    pub frame: usize,     // number of frames that had run when the config was changed
+    pub time: f64,        // simulation time when the config was changed
+    pub key_index: usize, // number of keys that had been pressed when the config was changed
+    pub config: OuterConfig,
+}
+
+/// Something that happened between frames of a 'Recording'
+pub enum ReplayInput {
+    Key(String),
+    Config(Box<OuterConfig>),
+}
+
+/// Everything needed to reproduce a game: its starting state, the timing of every frame, and
+/// every key pressed and config change made.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Recording {
+    pub seed: u32,
+    pub config: OuterConfig,
+    pub save: Option<SaveGame>,
+    pub frame_times: Vec<f64>, // wall-clock time of each frame
+    pub keys: Vec<RecordedKey>,
+    #[serde(default)]
+    pub configs: Vec<RecordedConfig>,
+    sim_time: f64,
+}
+
+impl Recording {
+    pub fn new(seed: u32, config: &OuterConfig) -> Self {
+        Recording {
+            seed: seed,
+            config: config.clone(),
+            save: None,
+            frame_times: Vec::new(),
+            keys: Vec::new(),
+            configs: Vec::new(),
+            sim_time: 0.0,
+        }
+    }
+
+    pub fn record_frame(&mut self, elapsed_time: f64) {
+        self.frame_times.push(elapsed_time);
+        self.sim_time += elapsed_time;
+    }
+
+    pub fn record_key(&mut self, key: &str) {
+        self.keys.push(RecordedKey {
+            frame: self.frame_times.len(),
+            time: self.sim_time,
+            key: key.to_string(),
+        });
+    }
+
+    pub fn record_config(&mut self, config: &OuterConfig) {
+        self.configs.push(RecordedConfig {
+            frame: self.frame_times.len(),
+            time: self.sim_time,
+            key_index: self.keys.len(),
+            config: config.clone(),
+        });
+    }
+
+    /// Return whether nothing has happened in the game yet
+    pub fn is_empty(&self) -> bool {
+        self.frame_times.is_empty() && self.keys.is_empty() && self.configs.is_empty()
+    }
+}
+
+/// Progress through playing back a 'Recording'
+pub struct Replay {
+    recording: Recording,
+    next_frame: usize,
+    next_key: usize,
+    next_config: usize,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Self {
+        Replay {
+            recording: recording,
+            next_frame: 0,
+            next_key: 0,
+            next_config: 0,
+        }
+    }
+
+    /// Return the keys pressed and configs changed before the next frame, in the order they
+    /// happened, and that frame's 'elapsed_time', or 'None' if there are no more frames.  Inputs
+    /// after the last frame are returned with 'None'.
+    pub fn next_frame(&mut self) -> (Vec<ReplayInput>, Option<f64>) {
+        let mut inputs = Vec::new();
+        loop {
+            // A config change comes before any key pressed after it
+            if let Some(change) = self.recording.configs.get(self.next_config) {
+                if change.frame <= self.next_frame && change.key_index <= self.next_key {
+                    inputs.push(ReplayInput::Config(Box::new(change.config.clone())));
+                    self.next_config += 1;
+                    continue;
+                }
+            }
+
+            match self.recording.keys.get(self.next_key) {
+                Some(key) if key.frame <= self.next_frame => {
+                    inputs.push(ReplayInput::Key(key.key.clone()));
+                    self.next_key += 1;
+                }
+                _ => break,
+            }
+        }
+
+        let elapsed_time = self.recording.frame_times.get(self.next_frame).copied();
+        if elapsed_time.is_some() {
+            self.next_frame += 1;
+        }
+
+        (inputs, elapsed_time)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next_frame >= self.recording.frame_times.len()
+            && self.next_key >= self.recording.keys.len()
+            && self.next_config >= self.recording.configs.len()
+    }
+}
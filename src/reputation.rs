@@ -0,0 +1,60 @@
+use crate::events::GameEvent;
+use crate::painter::{BackgroundConfig, TextConfig};
+
+use engine_p::interpolable::Pos2d;
+use serde::{Serialize,Deserialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReputationUiConfig {
+    pub pos: Pos2d,
+    pub bg: BackgroundConfig,
+    pub text: TextConfig,
+}
+
+/// How the restaurant's reputation changes, and how busy and generous it makes customers
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReputationGameConfig {
+    pub starting: f64,
+    pub max: f64,                     // reputation ranges from 0 to this
+    pub fast_serve_seconds: f64,      // serves quicker than this count as fast
+    pub fast_serve_gain: f64,
+    pub slow_serve_gain: f64,
+    pub expired_loss: f64,            // lost when a customer runs out of patience
+    pub missed_loss: f64,             // lost per order still waiting when the day ends
+    pub low_order_period_scale: f64,  // 'order_period' is multiplied by this at no reputation...
+    pub high_order_period_scale: f64, // ... and by this at 'max', and in between in between
+    pub max_tip: f64,                 // fraction of an order's price tipped at 'max' reputation
+}
+
+impl ReputationGameConfig {
+    /// Return the specified 'reputation' as a fraction of 'max'
+    pub fn level(&self, reputation: f64) -> f64 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+
+        (reputation / self.max).clamp(0.0, 1.0)
+    }
+
+    /// Return what 'order_period' is multiplied by at the specified 'reputation'
+    pub fn order_period_scale(&self, reputation: f64) -> f64 {
+        let level = self.level(reputation);
+        self.low_order_period_scale + (self.high_order_period_scale - self.low_order_period_scale) * level
+    }
+
+    /// Return the tip, on top of the specified 'price', given at the specified 'reputation'
+    pub fn tip(&self, price: i32, reputation: f64) -> i32 {
+        (price as f64 * self.max_tip * self.level(reputation)).round() as i32
+    }
+
+    /// Return how much the specified 'event' changes the reputation
+    pub fn change(&self, event: &GameEvent) -> f64 {
+        match event {
+            GameEvent::OrderServed { serve_time, .. } =>
+                if *serve_time <= self.fast_serve_seconds { self.fast_serve_gain } else { self.slow_serve_gain },
+            GameEvent::OrderExpired { .. } => -self.expired_loss,
+            GameEvent::OrderMissed => -self.missed_loss,
+            _ => 0.0,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::key_stats::KeyStats;
+use crate::stats::PeriodStats;
+use crate::traits::Image;
+
+use serde::{Serialize,Deserialize};
+
+/// Version written into new saves.  Bump this when the format changes, and give any new fields
+/// a '#[serde(default)]' so that older saves keep loading.
+pub const SAVE_VERSION: u32 = 1;
+
+/// The progress that carries over between days (and sessions)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SaveGame {
+    pub version: u32,
+    #[serde(default)]
+    pub difficulty: Option<usize>,      // 'None' in saves from before the difficulty was saved
+    pub money: i32,
+    pub max_money: i32,
+    #[serde(default)]
+    pub reputation: Option<f64>,        // 'None' in saves from before reputation was added
+    pub store_tiers: Vec<usize>,        // index of the next upgrade in each store upgrade sequence
+    pub ingredients: Vec<Image>,        // ingredients unlocked in the ingredient area
+    pub cookers_unlocked: Vec<i32>,     // 'num_unlocked' of each cooker, in config order
+    #[serde(default)]
+    pub lifetime_stats: PeriodStats,
+    #[serde(default)]
+    pub key_stats: KeyStats,
+}
+
+impl SaveGame {
+    /// Return an error if this save can't be loaded by this version of the game
+    pub fn check_version(&self) -> Result<(), String> {
+        if self.version > SAVE_VERSION {
+            return Err(format!("Save version {} is newer than supported version {}", self.version, SAVE_VERSION));
+        }
+
+        Ok(())
+    }
+}
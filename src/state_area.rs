@@ -1,12 +1,16 @@
 
+use crate::events::GameEvent;
 use crate::ingredients::{IngredientStack, MovableIngredient};
 use crate::painter::{BackgroundConfig, ProgressBarConfig, RingConfig, TextConfig};
+use crate::stats::PeriodStats;
 use crate::traits::{BaseGame, Image};
+use crate::word_source::WordPool;
 
 use engine_p::interpolable::{Interpolable, Pos2d};
 use serde::{Serialize,Deserialize};
 
 use std::f64::consts::PI;
+use std::rc::Rc;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct StateUiConfig {
@@ -22,6 +26,16 @@ pub struct StateUiConfig {
     pub progress: ProgressBarConfig,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DaySummaryUiConfig {
+    pub pos: Pos2d,
+    pub bg: BackgroundConfig,
+    pub title: TextConfig,
+    pub text: TextConfig,
+    pub width: f64,       // width the text is fit into
+    pub line_height: f64, // distance between lines of text
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct StateGameConfig {
     pub day_length: f64,
@@ -33,6 +47,7 @@ pub struct StateGameConfig {
 enum StoreState {
     Open,
     Closing,
+    Summary, // showing how the day went, before the store
     Closed,
 }
 
@@ -48,7 +63,7 @@ impl StateArea {
         clock_progress.set_end(cfg_game.day_length);
 
         let mut store_stack = IngredientStack::new(Interpolable::new(cfg_ui.pos, 1000.0));
-        store_stack.text = Some(game.word_bank().get_new_word());
+        store_stack.text = Some(game.word_bank().get_new_word(WordPool::State, game.rng()));
         store_stack.add_ingredient(MovableIngredient::new(Image::OpenSign, Interpolable::new((0,0).into(), 1000.0)), true, game);
 
         StateArea {
@@ -62,9 +77,18 @@ impl StateArea {
         if self.state == StoreState::Closing {
             if let Some(kw) = &self.open_close_store_stack.text {
                 if keywords.iter().any(|k| *k == **kw) {
-                    // Become 'closed' and show the store
-                    self.open_close_store_stack.text = Some(game.word_bank().get_new_word());
+                    // Stop the closing drain and show the day's summary
+                    self.open_close_store_stack.text = Some(game.word_bank().get_new_word(WordPool::State, game.rng()));
                     self.open_close_store_stack.progress = None;
+                    self.state = StoreState::Summary;
+                }
+            }
+        }
+        else if self.state == StoreState::Summary {
+            if let Some(kw) = &self.open_close_store_stack.text {
+                if keywords.iter().any(|k| *k == **kw) {
+                    // Become 'closed' and show the store
+                    self.open_close_store_stack.text = Some(game.word_bank().get_new_word(WordPool::State, game.rng()));
                     self.state = StoreState::Closed;
                 }
             }
@@ -74,8 +98,9 @@ impl StateArea {
                 if keywords.iter().any(|k| *k == **kw) {
                     // Restart the 'day' timer
                     self.clock_progress.set_cur(0.0);
-                    self.open_close_store_stack.text = Some(game.word_bank().get_new_word());
+                    self.open_close_store_stack.text = Some(game.word_bank().get_new_word(WordPool::State, game.rng()));
                     self.state = StoreState::Open;
+                    game.emit(GameEvent::DayStarted);
                 }
             }
         }
@@ -92,13 +117,15 @@ impl StateArea {
                 money_state.set_end(1.0);
 
                 self.open_close_store_stack.progress = Some(money_state);
+
+                game.emit(GameEvent::DayEnded);
             }
         }
         else if self.state == StoreState::Closing {
             // Advance the 'money down' timer
             if  let Some(money_progress) = &self.open_close_store_stack.progress {
                 if money_progress.advance(game.elapsed_time()) {
-                    game.add_money(cfg_game.money_down_amt);
+                    game.emit(GameEvent::MoneyDrained { amount: cfg_game.money_down_amt });
                     money_progress.set_cur(0.0);
                 }
             }
@@ -126,10 +153,56 @@ impl StateArea {
         }
     }
 
+    /// Draw the summary of the day described by the specified 'today' stats
+    pub fn draw_summary(&self, cfg: &DaySummaryUiConfig, today: &PeriodStats, game: &dyn BaseGame) {
+        let painter = game.painter();
+        painter.draw_area_background(&cfg.pos, &cfg.bg);
+        painter.draw_text("Day Summary", &cfg.pos, cfg.width, &cfg.title);
+
+        // One stat per line, so each fits the summary's width
+        let fmt_serve = |serve: Option<f64>| serve.map_or("-".to_string(), |t| format!("{:.1} s", t));
+        let rows: [(&str, String); 13] = [
+            ("Revenue", format!("$ {}", today.money_earned)),
+            ("Tips", format!("$ {}", today.tips_earned)),
+            ("Streak bonuses", format!("$ {}", today.streak_bonuses_earned)),
+            ("Orders served", today.orders_served.to_string()),
+            ("Orders missed", today.orders_missed.to_string()),
+            ("Customers who left", today.orders_expired.to_string()),
+            ("Fastest serve", fmt_serve(today.fastest_serve)),
+            ("Slowest serve", fmt_serve(today.slowest_serve)),
+            ("Best streak", today.longest_streak.to_string()),
+            ("Typing accuracy", format!("{:.0} %", today.accuracy() * 100.0)),
+            ("Delivered out of order", today.deliveries_rejected.to_string()),
+            ("Lost while closing", format!("$ {}", today.money_drained)),
+            ("Lost to leaving customers", format!("$ {}", today.money_lost_to_expiry)),
+        ];
+
+        for (i, (label, value)) in rows.iter().enumerate() {
+            let pos = cfg.pos + (0.0, cfg.line_height * (i + 1) as f64).into();
+            painter.draw_text(&format!("{}: {}", label, value), &pos, cfg.width, &cfg.text);
+        }
+    }
+
+    /// Append the keyword of the open/closed sign, if it's showing, to the specified 'keywords'
+    pub fn collect_keywords(&self, keywords: &mut Vec<Rc<String>>) {
+        if self.state != StoreState::Open {
+            keywords.extend(self.open_close_store_stack.text.iter().cloned());
+        }
+    }
+
     pub fn in_store(&self) -> bool {
         self.state == StoreState::Closed
     }
 
+    pub fn in_summary(&self) -> bool {
+        self.state == StoreState::Summary
+    }
+
+    /// Return whether the restaurant is showing, which it is until the day's summary
+    pub fn in_restaurant(&self) -> bool {
+        self.state == StoreState::Open || self.state == StoreState::Closing
+    }
+
     pub fn update_config(&mut self, cfg_ui: &StateUiConfig, cfg_game: &StateGameConfig) {
         self.open_close_store_stack.pos.set_end(cfg_ui.pos);
         self.clock_progress.set_end(cfg_game.day_length);
@@ -0,0 +1,290 @@
+use crate::events::GameEvent;
+use crate::key_stats::KeyStats;
+
+use serde::{Serialize,Deserialize};
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Counters for a stretch of play (a single day, or the player's lifetime).  Fields missing from
+/// older saves default to zero.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct PeriodStats {
+    pub play_time: f64,         // seconds spent in the restaurant (not the store)
+    pub keystrokes: u32,        // characters typed, not counting whitespace
+    pub backspaces: u32,        // Backspace, Ctrl+Backspace and Delete presses
+    pub typos: u32,             // keystrokes that made the current word match no keyword, or entered a line with none
+    pub commands: u32,          // times Enter was pressed, or a command was auto-submitted
+    pub rejected_commands: u32, // commands where nothing typed was a visible keyword
+    pub accepted_words: u32,    // typed words that were visible keywords
+    pub rejected_words: u32,
+    pub accepted_chars: u32,    // characters in 'accepted_words'
+    pub total_latency: f64,     // seconds from keywords appearing to being typed, summed
+    pub latency_samples: u32,
+    pub orders_served: u32,
+    pub orders_missed: u32,
+    pub orders_expired: u32,    // orders whose customer ran out of patience
+    pub deliveries_rejected: u32, // ingredients delivered to a strict order out of order
+    pub fastest_serve: Option<f64>, // seconds from an order being created to being served
+    pub slowest_serve: Option<f64>,
+    pub dishes_cooked: u32,
+    pub money_earned: i32,
+    pub tips_earned: i32,       // part of 'money_earned' given on top of order prices
+    pub streak_bonuses_earned: i32, // part of 'money_earned' from serving orders in a streak
+    pub longest_streak: u32,    // orders served in a row without a typo or losing a customer
+    pub money_spent: i32,
+    pub money_drained: i32,
+    pub money_lost_to_expiry: i32, // penalties for orders whose customer left
+    // The same counters, but only while auto-submit was on, so its accuracy can be compared
+    pub auto_submit_keystrokes: u32,
+    pub auto_submit_chars: u32, // characters in accepted words
+    pub auto_submits: u32,      // commands entered by typing a keyword rather than Enter
+}
+
+impl PeriodStats {
+    /// Words per minute, counting every 5 characters of accepted keywords as a word
+    pub fn wpm(&self) -> f64 {
+        if self.play_time <= 0.0 {
+            return 0.0;
+        }
+
+        (self.accepted_chars as f64 / 5.0) / (self.play_time / 60.0)
+    }
+
+    /// Fraction of keystrokes that ended up in accepted keywords
+    pub fn accuracy(&self) -> f64 {
+        if self.keystrokes == 0 {
+            return 1.0;
+        }
+
+        (self.accepted_chars as f64 / self.keystrokes as f64).min(1.0)
+    }
+
+    /// Like 'accuracy', but only for typing done while auto-submit was on
+    pub fn auto_submit_accuracy(&self) -> f64 {
+        if self.auto_submit_keystrokes == 0 {
+            return 1.0;
+        }
+
+        (self.auto_submit_chars as f64 / self.auto_submit_keystrokes as f64).min(1.0)
+    }
+
+    /// Average seconds from a keyword appearing to it being typed
+    pub fn average_latency(&self) -> f64 {
+        if self.latency_samples == 0 {
+            return 0.0;
+        }
+
+        self.total_latency / self.latency_samples as f64
+    }
+
+    fn report(&self) -> PeriodReport {
+        PeriodReport {
+            counters: self.clone(),
+            wpm: self.wpm(),
+            accuracy: self.accuracy(),
+            auto_submit_accuracy: self.auto_submit_accuracy(),
+            average_latency: self.average_latency(),
+        }
+    }
+}
+
+/// A 'PeriodStats' along with the values derived from it
+#[derive(Serialize, Clone, Debug)]
+pub struct PeriodReport {
+    #[serde(flatten)]
+    pub counters: PeriodStats,
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub auto_submit_accuracy: f64,
+    pub average_latency: f64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StatsReport {
+    pub today: PeriodReport,
+    pub days: Vec<PeriodReport>, // previous days, oldest first
+    pub lifetime: PeriodReport,
+}
+
+/// Tracks how the player is doing, for the current day and overall
+pub struct Stats {
+    today: PeriodStats,
+    days: Vec<PeriodStats>,
+    lifetime: PeriodStats,
+    time: f64,                            // simulated seconds since the game started
+    keyword_times: HashMap<String, f64>, // 'time' when each visible keyword appeared
+    keys: KeyStats,                       // over the player's lifetime
+    last_key_time: f64,                   // 'time' when a letter was last typed
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats {
+            today: PeriodStats::default(),
+            days: Vec::new(),
+            lifetime: PeriodStats::default(),
+            time: 0.0,
+            keyword_times: HashMap::new(),
+            keys: KeyStats::default(),
+            last_key_time: 0.0,
+        }
+    }
+
+    // Apply the specified 'update' to both the current day and the lifetime stats
+    fn update<F: Fn(&mut PeriodStats)>(&mut self, update: F) {
+        update(&mut self.today);
+        update(&mut self.lifetime);
+    }
+
+    /// Advance our clock by 'elapsed_time'.  Only time spent in the restaurant counts towards
+    /// play time.
+    pub fn advance(&mut self, elapsed_time: f64, in_restaurant: bool) {
+        self.time += elapsed_time;
+        if in_restaurant {
+            self.update(|s| s.play_time += elapsed_time);
+        }
+    }
+
+    /// Note which of the specified 'keywords' are newly visible, and forget ones that are gone
+    pub fn observe_keywords(&mut self, keywords: &[Rc<String>]) {
+        self.keyword_times.retain(|kw, _| keywords.iter().any(|k| **k == *kw));
+
+        for kw in keywords.iter() {
+            if !self.keyword_times.contains_key(kw.as_str()) {
+                self.keyword_times.insert(kw.to_string(), self.time);
+            }
+        }
+    }
+
+    /// Record the specified 'key' being pressed in the keyword entry, with 'auto_submit' on or off
+    pub fn record_key(&mut self, key: &str, auto_submit: bool) {
+        if key == "Backspace" || key == "Ctrl+Backspace" || key == "Delete" {
+            self.update(|s| s.backspaces += 1);
+        }
+        else if key.chars().count() == 1 && !key.trim().is_empty() {
+            self.update(|s| s.keystrokes += 1);
+            if auto_submit {
+                self.update(|s| s.auto_submit_keystrokes += 1);
+            }
+        }
+    }
+
+    /// Record the specified 'typed' letter, which was meant to be 'expected' if that's known, and
+    /// followed 'prev' in the same word.  'typo' is whether it made the word match no keyword.
+    pub fn record_letter(&mut self, typed: char, expected: Option<char>, prev: Option<char>, typo: bool) {
+        let interval = prev.map(|_| self.time - self.last_key_time);
+        self.last_key_time = self.time;
+
+        if !typo {
+            self.keys.record(typed, prev, true, interval);
+        }
+        else if let Some(expected) = expected {
+            self.keys.record(expected, prev, false, None);
+        }
+    }
+
+    /// Record the specified 'keywords' being entered, while 'visible' keywords were on screen and
+    /// 'auto_submit' was on or off.  'auto_submitted' is whether the command was entered without
+    /// pressing Enter.
+    pub fn record_command(&mut self, keywords: &[String], visible: &[Rc<String>], auto_submit: bool, auto_submitted: bool) {
+        let mut accepted = 0;
+        for kw in keywords.iter() {
+            if !visible.iter().any(|v| **v == *kw) {
+                self.update(|s| s.rejected_words += 1);
+                continue;
+            }
+
+            accepted += 1;
+            let chars = kw.chars().count() as u32;
+            self.update(|s| {
+                s.accepted_words += 1;
+                s.accepted_chars += chars;
+                if auto_submit {
+                    s.auto_submit_chars += chars;
+                }
+            });
+
+            if let Some(appeared) = self.keyword_times.remove(kw) {
+                let latency = self.time - appeared;
+                self.update(|s| {
+                    s.total_latency += latency;
+                    s.latency_samples += 1;
+                });
+            }
+        }
+
+        self.update(|s| s.commands += 1);
+        if auto_submitted {
+            self.update(|s| s.auto_submits += 1);
+        }
+        if accepted == 0 {
+            self.update(|s| s.rejected_commands += 1);
+        }
+    }
+
+    /// Record the player's serve streak reaching the specified 'streak'
+    pub fn record_streak(&mut self, streak: u32) {
+        self.update(|s| s.longest_streak = s.longest_streak.max(streak));
+    }
+
+    pub fn handle_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::OrderServed { price, tip, streak_bonus, serve_time } => self.update(|s| {
+                s.orders_served += 1;
+                s.money_earned += price + tip + streak_bonus;
+                s.tips_earned += tip;
+                s.streak_bonuses_earned += streak_bonus;
+                s.fastest_serve = Some(s.fastest_serve.map_or(*serve_time, |t| t.min(*serve_time)));
+                s.slowest_serve = Some(s.slowest_serve.map_or(*serve_time, |t| t.max(*serve_time)));
+            }),
+            GameEvent::OrderMissed => self.update(|s| s.orders_missed += 1),
+            GameEvent::OrderExpired { penalty, .. } => self.update(|s| {
+                s.orders_expired += 1;
+                s.money_lost_to_expiry += penalty;
+            }),
+            GameEvent::Typo => self.update(|s| s.typos += 1),
+            GameEvent::DeliveryRejected => self.update(|s| s.deliveries_rejected += 1),
+            GameEvent::CookingDone { .. } => self.update(|s| s.dishes_cooked += 1),
+            GameEvent::UpgradePurchased { cost, .. } => self.update(|s| s.money_spent += cost),
+            GameEvent::MoneyDrained { amount } => self.update(|s| s.money_drained -= amount),
+            GameEvent::DayStarted => {
+                let day = std::mem::take(&mut self.today);
+                self.days.push(day);
+            }
+            _ => {}
+        }
+    }
+
+    /// Return the stats for the current day, previous days, and the player's lifetime
+    pub fn report(&self) -> StatsReport {
+        StatsReport {
+            today: self.today.report(),
+            days: self.days.iter().map(|d| d.report()).collect(),
+            lifetime: self.lifetime.report(),
+        }
+    }
+
+    pub fn today(&self) -> &PeriodStats {
+        &self.today
+    }
+
+    pub fn lifetime(&self) -> &PeriodStats {
+        &self.lifetime
+    }
+
+    /// Replace the lifetime stats, e.g. with ones restored from a save
+    pub fn set_lifetime(&mut self, lifetime: &PeriodStats) {
+        self.lifetime = lifetime.clone();
+    }
+
+    pub fn keys(&self) -> &KeyStats {
+        &self.keys
+    }
+
+    /// Replace the per-key stats, e.g. with ones restored from a save
+    pub fn set_keys(&mut self, keys: &KeyStats) {
+        self.keys = keys.clone();
+    }
+}
@@ -1,8 +1,10 @@
 
+use crate::events::GameEvent;
 use crate::ingredients::{IngredientStack, MovableIngredient};
 use crate::painter::{BackgroundConfig, TextConfig};
 use crate::traits::{BaseGame, Image};
 use crate::utils::WordBank;
+use crate::word_source::WordPool;
 
 use engine_p::interpolable::{Interpolable, Pos2d};
 use serde::{Serialize,Deserialize};
@@ -49,7 +51,7 @@ impl UpgradeStore {
                 .iter()
                 .map(|_upgr| UpgradeStackInfo {
                     idx: 0,
-                    keyword: game.word_bank().get_new_word()
+                    keyword: game.word_bank().get_new_word(WordPool::Store, game.rng())
                 })
                 .collect(),
         }
@@ -87,28 +89,53 @@ impl UpgradeStore {
     }
 
     pub fn handle_command(&mut self, keywords: &Vec<String>, upgrades: &mut Vec<StoreUpgradeConfig>, word_bank: &WordBank, game: &dyn BaseGame, cfg: &StoreConfig) {
+        // Money is only spent once the purchase events are dispatched, so track it here
+        let mut money = game.get_money();
+
         for keyword in keywords.iter() {
             for (cfg_upgrs, upgr_info) in cfg.upgrades.iter().zip(self.upgrades.iter_mut()) {
-                if *upgr_info.keyword != **keyword {
+                // Sold out upgrades keep their last keyword, but it's no longer shown
+                if *upgr_info.keyword != **keyword || upgr_info.idx >= cfg_upgrs.len() {
                     continue;
                 }
 
                 let upgr = &cfg_upgrs[upgr_info.idx];
 
-                let money = game.get_money();
                 if money < upgr.cost {
                     continue;
                 }
 
-                game.add_money(-upgr.cost);
+                money -= upgr.cost;
+                game.emit(GameEvent::UpgradePurchased { img: upgr.img, action: upgr.action.clone(), cost: upgr.cost });
             
                 upgrades.push(upgr.clone());
                 upgr_info.idx += 1;
-                upgr_info.keyword = word_bank.get_new_word();
+                upgr_info.keyword = word_bank.get_new_word(WordPool::Store, game.rng());
+            }
+        }
+    }
+
+    /// Append the keywords of all upgrades still for sale to the specified 'keywords'
+    pub fn collect_keywords(&self, keywords: &mut Vec<Rc<String>>, cfg: &StoreConfig) {
+        for (cfg_upgrs, upgr_info) in cfg.upgrades.iter().zip(self.upgrades.iter()) {
+            if upgr_info.idx < cfg_upgrs.len() {
+                keywords.push(upgr_info.keyword.clone());
             }
         }
     }
 
+    /// Return the index of the next upgrade in each upgrade sequence
+    pub fn tiers(&self) -> Vec<usize> {
+        self.upgrades.iter().map(|upgr_info| upgr_info.idx).collect()
+    }
+
+    /// Set the index of the next upgrade in each upgrade sequence, without applying any upgrades
+    pub fn set_tiers(&mut self, tiers: &[usize], cfg: &StoreConfig) {
+        for ((cfg_upgrs, upgr_info), tier) in cfg.upgrades.iter().zip(self.upgrades.iter_mut()).zip(tiers.iter()) {
+            upgr_info.idx = (*tier).min(cfg_upgrs.len());
+        }
+    }
+
     pub fn unlock_all(&mut self, upgrades: &mut Vec<StoreUpgradeConfig>, cfg: &StoreConfig) {
         for (cfg_upgrs, upgr_info) in cfg.upgrades.iter().zip(self.upgrades.iter_mut()) {
             for upgr in cfg_upgrs {
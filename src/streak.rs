@@ -0,0 +1,100 @@
+use crate::painter::TextConfig;
+use crate::traits::BaseGame;
+
+use engine_p::interpolable::{Interpolable, Pos2d};
+use serde::{Serialize,Deserialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StreakUiConfig {
+    pub pos: Pos2d,
+    pub text: TextConfig,
+    pub min_streak: u32,  // streaks shorter than this aren't shown
+    pub pulse_scale: f64, // text grows to this much of its size when the streak grows...
+    pub pulse_speed: f64, // ... and shrinks back at this much of its size per second
+}
+
+/// Price multipliers for orders served back to back, without typos or customers leaving
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StreakGameConfig {
+    pub multipliers: Vec<f64>, // indexed by the streak before the serve.  The last one is used for longer streaks.
+}
+
+impl StreakGameConfig {
+    /// Return what an order's price is multiplied by when served during the specified 'streak'
+    pub fn multiplier(&self, streak: u32) -> f64 {
+        match self.multipliers.last() {
+            Some(last) => *self.multipliers.get(streak as usize).unwrap_or(last),
+            None => 1.0,
+        }
+    }
+
+    /// Return the bonus, on top of the specified 'price', for serving during the specified 'streak'
+    pub fn bonus(&self, price: i32, streak: u32) -> i32 {
+        (price as f64 * (self.multiplier(streak) - 1.0)).round() as i32
+    }
+}
+
+/// Shows the current streak, pulsing whenever it grows
+pub struct StreakIndicator {
+    pulse: Interpolable<f64>, // scale of the text
+}
+
+impl StreakIndicator {
+    pub fn new(cfg: &StreakUiConfig) -> Self {
+        StreakIndicator {
+            pulse: Interpolable::new(1.0, cfg.pulse_speed),
+        }
+    }
+
+    /// Start a pulse, for the streak growing
+    pub fn pulse(&self, cfg: &StreakUiConfig) {
+        self.pulse.set_cur(cfg.pulse_scale);
+        self.pulse.set_end(1.0);
+    }
+
+    pub fn think(&self, game: &dyn BaseGame) {
+        self.pulse.advance(game.elapsed_time());
+    }
+
+    /// Draw the specified 'streak', served with the specified 'multiplier'
+    pub fn draw(&self, streak: u32, multiplier: f64, cfg: &StreakUiConfig, game: &dyn BaseGame) {
+        if streak < cfg.min_streak {
+            return;
+        }
+
+        let mut text_cfg = cfg.text.clone();
+        text_cfg.size = (text_cfg.size as f64 * self.pulse.cur()).round() as i32;
+        game.painter().draw_text(&format!("Streak {}  x{:.2}", streak, multiplier), &cfg.pos, 0.0, &text_cfg);
+    }
+
+    pub fn update_config(&self, cfg: &StreakUiConfig) {
+        self.pulse.set_speed(cfg.pulse_speed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn streak_config() -> StreakGameConfig {
+        StreakGameConfig { multipliers: vec![1.0, 1.0, 1.5, 2.0] }
+    }
+
+    #[test]
+    fn multiplier_steps_up() {
+        let cfg = streak_config();
+        let multipliers: Vec<f64> = (0..6).map(|streak| cfg.multiplier(streak)).collect();
+        assert_eq!(multipliers, vec![1.0, 1.0, 1.5, 2.0, 2.0, 2.0]);
+
+        assert_eq!(StreakGameConfig { multipliers: Vec::new() }.multiplier(3), 1.0);
+    }
+
+    #[test]
+    fn bonus() {
+        let cfg = streak_config();
+        assert_eq!(cfg.bonus(10, 0), 0);
+        assert_eq!(cfg.bonus(10, 2), 5);
+        assert_eq!(cfg.bonus(7, 2), 4);
+        assert_eq!(cfg.bonus(10, 50), 10);
+    }
+}
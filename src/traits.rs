@@ -1,8 +1,9 @@
 
+use crate::events::GameEvent;
 use crate::painter::Painter;
-use crate::utils::WordBank;
+use crate::utils::{Rng, WordBank};
 
-use engine_p::sounds::Sounds;
+use engine_p::images::ImagesConfig;
 use serde::{Serialize,Deserialize};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -44,19 +45,90 @@ pub trait BaseGame {
 
     fn get_money(&self) -> i32;
 
-    fn add_money(&self, amt: i32);
+    fn get_reputation(&self) -> f64;
+
+    // Orders served in a row without a typo or losing a customer
+    fn get_streak(&self) -> u32;
 
     //fn config<'a>(&'a self) ->  &'a GameConfig;
 
     fn word_bank<'a>(&'a self) -> &'a WordBank;
 
+    // All random choices in the game go through this, so a game can be reproduced from its seed
+    fn rng(&self) -> &Rng;
+
     //fn images<'a>(&'a self) -> &'a Images;
 
     fn painter<'a>(&'a self) -> &'a Painter;
 
-    fn sounds(&self) -> &Sounds<Sound>;
+    // Report that something happened.  Money and sounds are only changed through events.
+    fn emit(&self, event: GameEvent);
 
     //fn image_props<'a>(&'a self, image: &Image) -> &'a ImageProps;
 
     fn elapsed_time(&self) -> f64;
-}
\ No newline at end of file
+}
+
+/// Dimensions of a piece of text, as measured by a 'DrawBackend'
+pub struct TextDims {
+    pub width: f64,
+    pub ascent: f64,
+    pub descent: f64,
+}
+
+/// The low-level drawing operations the 'Painter' is built on.  This mirrors the subset of the
+/// canvas 2d API that we use, so that the game can run either against a real canvas or headless.
+pub trait DrawBackend {
+    fn set_global_alpha(&self, alpha: f64);
+
+    fn set_fill_style(&self, style: &str);
+
+    fn set_stroke_style(&self, style: &str);
+
+    fn set_line_width(&self, width: f64);
+
+    fn set_font(&self, font: &str);
+
+    fn set_text_baseline(&self, baseline: &str);
+
+    fn begin_path(&self);
+
+    fn close_path(&self);
+
+    fn rect(&self, x: f64, y: f64, width: f64, height: f64);
+
+    fn round_rect(&self, x: f64, y: f64, width: f64, height: f64, radius: f64);
+
+    fn arc(&self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool);
+
+    fn fill(&self);
+
+    fn stroke(&self);
+
+    fn clip(&self);
+
+    fn save(&self);
+
+    fn restore(&self);
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64);
+
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64);
+
+    fn fill_text(&self, text: &str, x: f64, y: f64);
+
+    fn stroke_text(&self, text: &str, x: f64, y: f64);
+
+    fn measure_text(&self, text: &str) -> TextDims;
+
+    fn draw_image(&self, image: &Image, x: f64, y: f64, gray: bool);
+
+    fn image_width(&self, image: &Image) -> f64;
+
+    fn image_height(&self, image: &Image) -> f64;
+
+    fn update_images_config(&mut self, cfg: &ImagesConfig<Image>);
+
+    // Show everything drawn this frame
+    fn present(&self);
+}
@@ -1,7 +1,13 @@
+use crate::alphabet::AlphabetGameConfig;
+use crate::word_score::score_word;
+use crate::word_source::{SourceWord, WordPool, WordPoolsConfig};
+use crate::GameConfig;
 use itertools::Itertools;
-use std::collections::HashSet;
+use serde::{Serialize,Deserialize};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
-use js_sys::Math;
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
 pub fn set_panic_hook() {
@@ -15,30 +21,106 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
+    pub fn log(s: &str);
+}
+
+// Outside of the browser there's no console to log to, so just print to stderr
+#[cfg(not(target_arch = "wasm32"))]
+pub fn log(s: &str) {
+    eprintln!("{}", s);
+}
+
+// Pick a seed for an 'Rng', for when the user doesn't specify one
+#[cfg(target_arch = "wasm32")]
+pub fn random_seed() -> u32 {
+    (js_sys::Math::random() * (u32::MAX as f64)) as u32
+}
+
+// Pick a seed for an 'Rng', for when the user doesn't specify one.  'Math.random' isn't available
+// natively, so use the clock.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn random_seed() -> u32 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos() ^ d.as_secs() as u32).unwrap_or(0)
+}
+
+// Deterministic random number generator (SplitMix64), so that a game can be reproduced from its
+// seed.  Uses a 'Cell' so it can be used through a shared 'BaseGame'.
+pub struct Rng {
+    state: Cell<u64>,
+}
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Rng {
+            state: Cell::new(seed as u64),
+        }
+    }
+
+    pub fn next_u64(&self) -> u64 {
+        let mut z = self.state.get().wrapping_add(0x9E3779B97F4A7C15);
+        self.state.set(z);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // Return a random number in [0, 1)
+    pub fn random(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Return a random index in [0, 'len').  'len' must be positive.
+    pub fn random_index(&self, len: usize) -> usize {
+        assert!(len > 0, "Can't pick a random index from an empty range");
+        ((self.random() * (len as f64)) as usize).min(len - 1)
+    }
+}
+
+/// How a 'WordBank' picks new words, relative to the ones already in use
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum KeywordAllocation {
+    Any,             // any word that isn't already in use
+    Distinguishable, // a word whose first letter no visible keyword shares if possible, and
+                     // otherwise one that can be told apart from them by the shortest prefix
+}
+
+// Return the number of leading characters 'a' and 'b' have in common, as matched by 'alphabet'
+fn shared_prefix_len(a: &str, b: &str, alphabet: &AlphabetGameConfig) -> usize {
+    a.chars().zip(b.chars()).take_while(|(x, y)| alphabet.normalize_char(*x) == alphabet.normalize_char(*y)).count()
+}
+
+// Words a 'WordBank' can pick from for one 'WordLevelConfig'
+struct WordLevel {
+    weight: f64,
+    words: Vec<usize>, // indices into 'WordBank::words'
 }
 
 pub struct WordBank {
-    words: Vec<Rc<String>>,
+    words: Vec<Rc<String>>, // every word in any level, once each
+    tags: Vec<Vec<String>>, // tags of each of 'words'
+    levels: Vec<WordLevel>,
+    pools: WordPoolsConfig,
+    allocation: KeywordAllocation,
+    alphabet: AlphabetGameConfig,
+    bias: HashMap<String, f64>, // extra weight for words containing each (normalized) letter or bigram
+    visible: RefCell<Vec<String>>, // keywords on screen, which new words should be told apart from
 }
 
 impl WordBank {
-    // Create a WordBank with words at the specified 'word_level' using the specified 'words_db'
-    // and excluding any words in the specified 'bad_words_db'. 'words_db' 
-    // contains a word per line, along with a 'frequency' count of how often that
-    // word is used.
-    pub fn new(words_db: &String, bad_words_db: &String, word_level: usize) -> Self {
-        if word_level == 0 {
-            // Level 0 is just 2-letter combinations of characters
-            return WordBank {
-                words: ('a'..'z').cartesian_product('a'..'z')
-                       .map(|e| Rc::new([e.0, e.1].iter().collect()))
-                       .collect()
-            };
-        }
+    // Create a WordBank with words from the current difficulty's levels in the specified 'cfg'
+    // using the specified 'source_words' (see 'word_source::load_words') and excluding any words
+    // in the specified 'bad_words_db'.  Only words made of the alphabet's letters are used.
+    // Return an error if a level that can be picked, or a pool of dictionary words, has no words.
+    pub fn new(source_words: &[SourceWord], bad_words_db: &String, cfg: &GameConfig) -> Result<Self, String> {
+        let levels = &cfg.difficulty().word_levels;
+        let score_cfg = &cfg.word_score;
+        let alphabet = cfg.alphabet();
 
         let mut bad_words: HashSet<String> = HashSet::new();
         for line in bad_words_db.split('\n') {
@@ -49,70 +131,322 @@ impl WordBank {
             bad_words.insert(line.to_string());
         }
 
-        let mut processed_words= 0;
-        let mut ret:Vec<Rc<String>> = Vec::new();
-        for line in words_db.split('\n') {
-            if line.len() == 0 {
-                continue;
-            }
-            let mut line_iter = line.split(' ');
-            let word = line_iter.next().unwrap();
-
-            let mut skip_word = false;
-            for char in word.chars() {
-                if char  < 'a' || char > 'z' {
-                    skip_word = true;
-                    break;
-                }
-            }
+        // Scores and tags of the dictionary words, and of the 2-letter combinations of characters
+        let mut dict_words: Vec<(String, f64, Vec<String>)> = Vec::new();
+        for source_word in source_words.iter() {
+            let word = source_word.word.as_str();
 
-            if skip_word {
+            if !alphabet.allows(word) {
                 continue;
             }
 
             if word.chars().nth(0).unwrap() == '\'' ||  // skip entries starting with apostrophe
-            word.len() == 1 || // skip single character "words"
-            bad_words.contains(word) // skip bad words
+            word.chars().count() == 1 || // skip single character "words"
+            word.split(' ').any(|part| bad_words.contains(&part.to_lowercase())) // skip bad words
             { 
                 continue;
             }
 
-            processed_words += 1;
+            let score = score_word(word, source_word.rank, score_cfg);
+            dict_words.push((word.to_string(), score, source_word.tags.clone()));
+        }
 
-            // Figure out the word's difficulty, from 1(easiest) to 5(hardest)
-            // for 4+ chars, difficulty is 'len - 4'
-            // words within 70% of max_count get +0, 50% +1, 30% +2, <30% +3
+        let letter_pairs: Vec<(String, f64, Vec<String>)> = alphabet.letters.chars().cartesian_product(alphabet.letters.chars())
+            .map(|e| [e.0, e.1].iter().collect::<String>())
+            .map(|w| { let score = score_word(&w, 0, score_cfg); (w, score, Vec::new()) })
+            .collect();
+
+        // A phrase scores the total of its words' scores.  Words not in any list are scored as if
+        // they were the least common.
+        let ranks: HashMap<&str, usize> = source_words.iter().map(|w| (w.word.as_str(), w.rank)).collect();
+        let least_common = source_words.iter().map(|w| w.rank + 1).max().unwrap_or(0);
+        let score_phrase = |phrase: &str| -> Option<f64> {
+            let mut score = 0.0;
+            for word in phrase.split_whitespace() {
+                if !alphabet.allows(word) || bad_words.contains(&word.to_lowercase()) {
+                    return None;
+                }
+                score += score_word(word, ranks.get(word).copied().unwrap_or(least_common), score_cfg);
+            }
+            Some(score)
+        };
+
+        let mut words: Vec<Rc<String>> = Vec::new();
+        let mut tags: Vec<Vec<String>> = Vec::new();
+        let mut word_indices: HashMap<String, usize> = HashMap::new();
+        let mut bank_levels: Vec<WordLevel> = Vec::new();
+        for (i, level) in levels.iter().enumerate() {
+            let phrases: Vec<(String, f64, Vec<String>)> = level.phrases.iter()
+                .filter_map(|p| score_phrase(p).map(|score| (p.split_whitespace().join(" "), score, Vec::new())))
+                .collect();
+            let source = if !phrases.is_empty() { &phrases } else if level.letter_pairs { &letter_pairs } else { &dict_words };
+
+            let mut level_words: Vec<usize> = Vec::new();
+            for (word, score, word_tags) in source.iter() {
+                if !level.contains(*score) {
+                    continue;
+                }
 
-            let mut word_score = processed_words/20000 + 1;
-            if word.len() > 4 {
-                word_score += word.len() - 4;
+                let idx = *word_indices.entry(word.clone()).or_insert_with(|| {
+                    words.push(Rc::new(word.clone()));
+                    tags.push(word_tags.clone());
+                    words.len() - 1
+                });
+                level_words.push(idx);
             }
 
-            if word_score > 5 {
-                word_score = 5;
+            if level_words.is_empty() && level.weight > 0.0 {
+                return Err(format!("Word level {} has no words scoring from {} to {}", i, level.min_score, level.max_score));
+            }
+
+            bank_levels.push(WordLevel {
+                weight: level.weight,
+                words: level_words,
+            });
+        }
+
+        // Letter pairs and phrases have no tags, so pools only pick between dictionary words
+        let uses_dict = levels.iter().any(|l| l.phrases.is_empty() && !l.letter_pairs);
+        for pool in [WordPool::Orders, WordPool::Ingredients, WordPool::Cookers, WordPool::Store, WordPool::State] {
+            let pool_tags = cfg.word_pools().tags(pool);
+            if uses_dict && !pool_tags.is_empty() && !tags.iter().any(|t| t.iter().any(|tag| pool_tags.contains(tag))) {
+                return Err(format!("No words are tagged with any of {:?} for {:?}", pool_tags, pool));
+            }
+        }
+
+        Ok(WordBank {
+            words: words,
+            tags: tags,
+            levels: bank_levels,
+            pools: cfg.word_pools().clone(),
+            allocation: cfg.difficulty().keyword_allocation,
+            alphabet: alphabet.clone(),
+            bias: HashMap::new(),
+            visible: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Favor words containing the letters and bigrams in the specified 'bias'.  A word's chance of
+    /// being picked is proportional to 1 plus the bias of each letter and bigram in it.
+    pub fn set_bias(&mut self, bias: HashMap<String, f64>) {
+        self.bias = bias;
+    }
+
+    // Return how likely the word at the specified 'idx' is to be picked, relative to a word with
+    // no bias
+    fn word_weight(&self, idx: usize) -> f64 {
+        let chars: Vec<char> = self.words[idx].chars().map(|c| self.alphabet.normalize_char(c)).collect();
+
+        let mut weight = 1.0;
+        for c in chars.iter() {
+            weight += self.bias.get(&c.to_string()).copied().unwrap_or(0.0);
+        }
+        for pair in chars.windows(2) {
+            weight += self.bias.get(&pair.iter().collect::<String>()).copied().unwrap_or(0.0);
+        }
+
+        weight.max(0.0)
+    }
+
+    // Pick one of the words at the specified 'idxs', by 'word_weight'
+    fn choose_word(&self, idxs: &[usize], rng: &Rng) -> Rc<String> {
+        if self.bias.is_empty() {
+            return self.words[idxs[rng.random_index(idxs.len())]].clone();
+        }
+
+        let weights: Vec<f64> = idxs.iter().map(|idx| self.word_weight(*idx)).collect();
+        let mut word_selector = rng.random() * weights.iter().sum::<f64>();
+        for (idx, weight) in idxs.iter().zip(weights.iter()) {
+            word_selector -= weight;
+            if word_selector <= 0.0 {
+                return self.words[*idx].clone();
             }
+        }
+
+        self.words[idxs[idxs.len() - 1]].clone()
+    }
+
+    /// Set the keywords currently on screen.  Words returned by 'get_new_word' are added to them
+    /// until they're set again.
+    pub fn set_visible(&self, keywords: &[Rc<String>]) {
+        *self.visible.borrow_mut() = keywords.iter().map(|kw| kw.to_string()).collect();
+    }
+
+    /// Return a new word for the specified 'pool'.  If none of its words are free, any word is used.
+    pub fn get_new_word(&self, pool: WordPool, rng: &Rng) -> Rc<String> {
+        let (level, pool) = match self.choose_level(Some(pool), rng) {
+            Some(level) => (level, Some(pool)),
+            None => match self.choose_level(None, rng) {
+                Some(level) => (level, None),
+                None => (&self.levels[rng.random_index(self.levels.len())], None), // every word is in use
+            },
+        };
+
+        let word = match self.allocation {
+            KeywordAllocation::Any => self.get_unused_word(level, pool, rng),
+            KeywordAllocation::Distinguishable => self.get_distinguishable_word(level, pool, rng),
+        };
+        self.visible.borrow_mut().push(word.to_string());
 
-            if word_score == word_level {
-                ret.push(Rc::new(word.to_string()));
+        word
+    }
+
+    // Return whether the word at the specified 'idx' is in use
+    fn is_used(&self, idx: usize) -> bool {
+        Rc::strong_count(&self.words[idx]) > 1
+    }
+
+    // Return whether the word at the specified 'idx' can be used for 'pool', or any pool if 'None'
+    fn in_pool(&self, idx: usize, pool: Option<WordPool>) -> bool {
+        let pool_tags = match pool {
+            Some(pool) => self.pools.tags(pool),
+            None => return true,
+        };
+
+        pool_tags.is_empty() || self.tags[idx].iter().any(|tag| pool_tags.contains(tag))
+    }
+
+    // Return whether the word at the specified 'idx' is free to be used for 'pool'
+    fn is_available(&self, idx: usize, pool: Option<WordPool>) -> bool {
+        !self.is_used(idx) && self.in_pool(idx, pool)
+    }
+
+    // Pick a level to take a word from, by weight, out of those with words that are available for
+    // 'pool', if any are
+    fn choose_level(&self, pool: Option<WordPool>, rng: &Rng) -> Option<&WordLevel> {
+        let levels: Vec<&WordLevel> = self.levels.iter()
+            .filter(|l| l.weight > 0.0 && l.words.iter().any(|idx| self.is_available(*idx, pool)))
+            .collect();
+        if levels.is_empty() {
+            return None;
+        }
+
+        let total_weight: f64 = levels.iter().map(|l| l.weight).sum();
+        let mut level_selector = rng.random() * total_weight;
+        for level in levels.iter() {
+            level_selector -= level.weight;
+            if level_selector <= 0.0 {
+                return Some(level);
             }
         }
 
-        WordBank {
-            words: ret,
+        Some(levels[levels.len() - 1])
+    }
+
+    // Return a random word from 'level' that's available for 'pool', or any word from it if none
+    // are
+    fn get_unused_word(&self, level: &WordLevel, pool: Option<WordPool>, rng: &Rng) -> Rc<String> {
+        let unused: Vec<usize> = level.words.iter().copied().filter(|idx| self.is_available(*idx, pool)).collect();
+        if unused.is_empty() {
+            return self.choose_word(&level.words, rng);
         }
+
+        self.choose_word(&unused, rng)
     }
 
-    pub fn get_new_word(&self) -> Rc<String> {
-        let mut idx: usize;
-        loop {
-            idx = (Math::random() *(self.words.len() as f64)) as usize;
-            //idx = rng.gen_range(0, self.words.len());
-            if Rc::strong_count(&self.words[idx]) == 1 {
-                break;
+    // Return a random word from 'level' that's available for 'pool', out of those sharing the
+    // shortest prefix with any visible keyword.  A shared prefix of 0 means the first letter is
+    // unique.
+    fn get_distinguishable_word(&self, level: &WordLevel, pool: Option<WordPool>, rng: &Rng) -> Rc<String> {
+        let visible = self.visible.borrow();
+
+        let mut best_shared = usize::MAX;
+        let mut candidates: Vec<usize> = Vec::new();
+        for idx in level.words.iter().copied().filter(|idx| self.is_available(*idx, pool)) {
+            let word = &self.words[idx];
+            let shared = visible.iter().map(|v| shared_prefix_len(word, v, &self.alphabet)).max().unwrap_or(0);
+            if shared < best_shared {
+                best_shared = shared;
+                candidates.clear();
+            }
+            if shared == best_shared {
+                candidates.push(idx);
             }
         }
 
-        return self.words[idx].clone();
+        if candidates.is_empty() {
+            // Every word is in use, so there's no better choice to make
+            return self.get_unused_word(level, pool, rng);
+        }
+
+        self.choose_word(&candidates, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_config;
+
+    // Return a bank of every pair of letters, picked as distinguishably as possible
+    fn letter_pairs_bank() -> WordBank {
+        let mut cfg = test_config().game;
+        cfg.difficulty = 0;
+        assert_eq!(cfg.difficulty().keyword_allocation, KeywordAllocation::Distinguishable);
+
+        WordBank::new(&[], &String::new(), &cfg).unwrap()
+    }
+
+    // Return a bank of the specified words, each with the specified tag
+    fn tagged_bank(words: &[(&str, &str)]) -> WordBank {
+        let mut cfg = test_config().game;
+        cfg.difficulty = 1;
+        cfg.difficulties[1].word_levels[0].max_score = 1000.0;
+        cfg.difficulties[1].word_levels[1].weight = 0.0;
+
+        let source_words: Vec<SourceWord> = words.iter()
+            .map(|(word, tag)| SourceWord { word: word.to_string(), tags: vec![tag.to_string()], rank: 0 })
+            .collect();
+        WordBank::new(&source_words, &String::new(), &cfg).unwrap()
+    }
+
+    #[test]
+    fn pools_pick_tagged_words() {
+        let bank = tagged_bank(&[("egg", "food"), ("ham", "food"), ("dog", "general"), ("cat", "general"), ("sun", "general")]);
+        let rng = Rng::new(3);
+
+        let mut ings: Vec<Rc<String>> = (0..2).map(|_| bank.get_new_word(WordPool::Ingredients, &rng)).collect();
+        ings.sort();
+        assert_eq!(ings, vec![Rc::new("egg".to_string()), Rc::new("ham".to_string())]);
+
+        let order = bank.get_new_word(WordPool::Orders, &rng);
+        assert!(["dog", "cat", "sun"].contains(&order.as_str()));
+
+        // Once every food word is in use, any free word is used
+        let ing = bank.get_new_word(WordPool::Ingredients, &rng);
+        assert!(["dog", "cat", "sun"].contains(&ing.as_str()) && ing != order);
+    }
+
+    #[test]
+    fn distinguishable_from_visible() {
+        let bank = letter_pairs_bank();
+        let rng = Rng::new(1);
+
+        // Every first letter but 'q' and 'x' is taken
+        let visible: Vec<Rc<String>> = ('a'..='z').filter(|c| *c != 'q' && *c != 'x').map(|c| Rc::new(format!("{}a", c))).collect();
+        bank.set_visible(&visible);
+
+        let first = bank.get_new_word(WordPool::Orders, &rng);
+        let second = bank.get_new_word(WordPool::Orders, &rng);
+        let mut starts = vec![first.chars().next().unwrap(), second.chars().next().unwrap()];
+        starts.sort();
+        assert_eq!(starts, vec!['q', 'x']);
+    }
+
+    #[test]
+    fn hidden_words_dont_count() {
+        let bank = letter_pairs_bank();
+        let rng = Rng::new(2);
+
+        // Words kept off screen, like the sign's while the restaurant is open, don't stop new words
+        // sharing their first letter.  These take every first letter but one.
+        let hidden: Vec<Rc<String>> = (0..25).map(|_| bank.get_new_word(WordPool::State, &rng)).collect();
+        let free = ('a'..='z').find(|c| !hidden.iter().any(|h| h.starts_with(*c))).unwrap();
+        bank.set_visible(&[Rc::new(format!("{}a", free))]);
+
+        let word = bank.get_new_word(WordPool::Orders, &rng);
+        assert!(!word.starts_with(free));
+        assert!(hidden.iter().all(|h| *h != word));
     }
 }
 
@@ -0,0 +1,531 @@
+use crate::keyword_entry::TypoPenalty;
+use crate::store::StoreUpgradeAction;
+use crate::traits::Image;
+use crate::OuterConfig;
+
+use serde::Serialize;
+
+use std::collections::HashSet;
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    Error,   // the game can't run with this config
+    Warning, // the game runs, but probably not as intended
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub enum ConfigIssueKind {
+    ParseError,
+    MissingImage(Image),
+    ZeroGridWidth,
+    NoVisibleOrders,
+    UnlockedCountOutOfRange { num_unlocked: i32, instances: usize },
+    NonPositiveValue(f64),
+    StartingMoneyAboveMax,
+    NoOrders,
+    NoOrdersAtStart,
+    OrderIngredientUnavailable(Image),
+    RecipeInputUnavailable(Image),
+    CookerNeverUnlocked(Image),
+    NoCookerForUpgrade(Image),
+    UpgradeHasNoEffect(Image),
+    UnknownDifficulty { difficulty: usize, difficulties: usize },
+    NoWordLevels,
+    NegativeValue(f64),
+    EmptyAlphabet,
+    NoWordSources,
+    ReputationOutOfRange { starting: f64, max: f64 },
+    EmptyScoreRange { min_score: f64, max_score: f64 },
+}
+
+/// A problem found in an 'OuterConfig', with the JSON path of the offending value
+#[derive(Serialize, Clone, Debug)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    pub path: String,
+    pub kind: ConfigIssueKind,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn parse_error(message: String) -> Self {
+        ConfigIssue {
+            severity: Severity::Error,
+            path: "".to_string(),
+            kind: ConfigIssueKind::ParseError,
+            message: message,
+        }
+    }
+}
+
+/// Return whether any of the specified 'issues' are errors
+pub fn has_errors(issues: &[ConfigIssue]) -> bool {
+    issues.iter().any(|issue| issue.severity == Severity::Error)
+}
+
+/// Describe all the errors in the specified 'issues', one per line
+pub fn describe_errors(issues: &[ConfigIssue]) -> String {
+    issues.iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .map(|issue| format!("{}: {}", issue.path, issue.message))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+struct Validator {
+    issues: Vec<ConfigIssue>,
+}
+
+impl Validator {
+    fn error(&mut self, path: String, kind: ConfigIssueKind, message: String) {
+        self.issues.push(ConfigIssue { severity: Severity::Error, path, kind, message });
+    }
+
+    fn warning(&mut self, path: String, kind: ConfigIssueKind, message: String) {
+        self.issues.push(ConfigIssue { severity: Severity::Warning, path, kind, message });
+    }
+
+    fn check_positive(&mut self, path: String, value: f64) {
+        if value <= 0.0 {
+            self.warning(path, ConfigIssueKind::NonPositiveValue(value), format!("Expected a positive value, got {}", value));
+        }
+    }
+}
+
+// Return the ingredients that can be obtained with the specified 'cfg'.  If 'with_store' is set,
+// include everything that can be bought in the store, otherwise only what's available at the start.
+fn obtainable_ingredients(cfg: &OuterConfig, with_store: bool) -> HashSet<Image> {
+    let mut ings: HashSet<Image> = cfg.game.ingredient_area.ingredients.iter().copied().collect();
+    let mut cookers_unlocked: Vec<bool> = cfg.ui.preparation_area.cookers.iter().map(|c| c.num_unlocked > 0).collect();
+
+    if with_store || cfg.game.unlock_all {
+        for upgr in cfg.ui.store.upgrades.iter().flatten() {
+            match upgr.action {
+                StoreUpgradeAction::UnlockIngredient => {
+                    ings.insert(upgr.img);
+                }
+                StoreUpgradeAction::UnlockCooker => {
+                    for (cooker, unlocked) in cfg.ui.preparation_area.cookers.iter().zip(cookers_unlocked.iter_mut()) {
+                        if cooker.base_image == upgr.img {
+                            *unlocked = true;
+                        }
+                    }
+                }
+                StoreUpgradeAction::IncreaseLimit => {}
+            }
+        }
+    }
+
+    // Keep cooking until nothing new can be made
+    loop {
+        let mut changed = false;
+        for (cooker, _) in cfg.ui.preparation_area.cookers.iter().zip(cookers_unlocked.iter()).filter(|(_, unlocked)| **unlocked) {
+            for recipe in cooker.recipes.iter() {
+                if recipe.inputs.iter().all(|ing| ings.contains(ing)) {
+                    for output in recipe.outputs.iter() {
+                        changed |= ings.insert(*output);
+                    }
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    ings
+}
+
+fn check_images(v: &mut Validator, cfg: &OuterConfig) {
+    let known: HashSet<Image> = cfg.ui.images.images.iter().map(|img| img.image).collect();
+
+    let mut used: Vec<(String, Image)> = vec![
+        ("ui.state".to_string(), Image::OpenSign),
+    ];
+
+    for (i, ing) in cfg.game.ingredient_area.ingredients.iter().enumerate() {
+        used.push((format!("game.ingredient_area.ingredients[{}]", i), *ing));
+    }
+
+    for (i, cooker) in cfg.ui.preparation_area.cookers.iter().enumerate() {
+        let path = format!("ui.preparation_area.cookers[{}]", i);
+        used.push((format!("{}.base_image", path), cooker.base_image));
+        for (j, recipe) in cooker.recipes.iter().enumerate() {
+            for (k, ing) in recipe.inputs.iter().enumerate() {
+                used.push((format!("{}.recipes[{}].inputs[{}]", path, j, k), *ing));
+            }
+            for (k, ing) in recipe.outputs.iter().enumerate() {
+                used.push((format!("{}.recipes[{}].outputs[{}]", path, j, k), *ing));
+            }
+        }
+    }
+
+    for (i, order) in cfg.ui.order_bar.orders.iter().enumerate() {
+        for (j, ing) in order.ings.iter().enumerate() {
+            used.push((format!("ui.order_bar.orders[{}].ings[{}].ing", i, j), ing.ing));
+        }
+    }
+
+    for (i, upgrs) in cfg.ui.store.upgrades.iter().enumerate() {
+        for (j, upgr) in upgrs.iter().enumerate() {
+            used.push((format!("ui.store.upgrades[{}][{}].img", i, j), upgr.img));
+            used.push((format!("ui.store.upgrades[{}][{}].overlay", i, j), upgr.overlay));
+        }
+    }
+
+    for (path, img) in used.into_iter() {
+        if !known.contains(&img) {
+            v.error(path, ConfigIssueKind::MissingImage(img), format!("{:?} isn't in ui.images", img));
+        }
+    }
+}
+
+fn check_values(v: &mut Validator, cfg: &OuterConfig) {
+    if cfg.ui.ingredient_area.grid_width == 0 {
+        v.error("ui.ingredient_area.grid_width".to_string(), ConfigIssueKind::ZeroGridWidth, "Grid width must be at least 1".to_string());
+    }
+
+    for (i, cooker) in cfg.ui.preparation_area.cookers.iter().enumerate() {
+        let instances = cooker.instances.len();
+        if cooker.num_unlocked < 0 || cooker.num_unlocked as usize > instances {
+            v.error(format!("ui.preparation_area.cookers[{}].num_unlocked", i),
+                    ConfigIssueKind::UnlockedCountOutOfRange { num_unlocked: cooker.num_unlocked, instances },
+                    format!("{} unlocked, but there are {} instances", cooker.num_unlocked, instances));
+        }
+
+        for (j, recipe) in cooker.recipes.iter().enumerate() {
+            v.check_positive(format!("ui.preparation_area.cookers[{}].recipes[{}].cook_time", i, j), recipe.cook_time);
+        }
+    }
+
+    for (i, order) in cfg.ui.order_bar.orders.iter().enumerate() {
+        v.check_positive(format!("ui.order_bar.orders[{}].weight", i), order.weight);
+        v.check_positive(format!("ui.order_bar.orders[{}].depreciation_seconds", i), order.depreciation_seconds);
+        // The patience bar can't count down without a positive patience
+        if order.patience_seconds <= 0.0 {
+            v.error(format!("ui.order_bar.orders[{}].patience_seconds", i), ConfigIssueKind::NonPositiveValue(order.patience_seconds),
+                    format!("Expected a positive value, got {}", order.patience_seconds));
+        }
+        if order.leave_penalty < 0 {
+            v.error(format!("ui.order_bar.orders[{}].leave_penalty", i), ConfigIssueKind::NegativeValue(order.leave_penalty as f64),
+                    format!("Expected zero or more, got {}", order.leave_penalty));
+        }
+    }
+
+    if cfg.game.timestep.tick_seconds <= 0.0 {
+        v.error("game.timestep.tick_seconds".to_string(), ConfigIssueKind::NonPositiveValue(cfg.game.timestep.tick_seconds),
+                format!("Expected a positive value, got {}", cfg.game.timestep.tick_seconds));
+    }
+
+    if cfg.game.timestep.max_ticks_per_frame == 0 {
+        v.error("game.timestep.max_ticks_per_frame".to_string(), ConfigIssueKind::NonPositiveValue(0.0),
+                "At least one tick must run per frame".to_string());
+    }
+
+    let difficulty = cfg.game.difficulty;
+    if difficulty >= cfg.game.difficulties.len() {
+        v.error("game.difficulty".to_string(),
+                ConfigIssueKind::UnknownDifficulty { difficulty, difficulties: cfg.game.difficulties.len() },
+                format!("Difficulty {} has no entry in game.difficulties, which has {}", difficulty, cfg.game.difficulties.len()));
+    }
+
+    for (i, diff) in cfg.game.difficulties.iter().enumerate() {
+        if diff.word_levels.is_empty() {
+            v.error(format!("game.difficulties[{}].word_levels", i), ConfigIssueKind::NoWordLevels,
+                    "At least one word level is needed to pick keywords from".to_string());
+        }
+
+        for (j, level) in diff.word_levels.iter().enumerate() {
+            let path = format!("game.difficulties[{}].word_levels[{}]", i, j);
+            v.check_positive(format!("{}.weight", path), level.weight);
+            if level.min_score >= level.max_score {
+                v.error(path, ConfigIssueKind::EmptyScoreRange { min_score: level.min_score, max_score: level.max_score },
+                        format!("No score is at least {} and below {}", level.min_score, level.max_score));
+            }
+        }
+    }
+
+    if cfg.game.alphabet.letters.is_empty() {
+        v.error("game.alphabet.letters".to_string(), ConfigIssueKind::EmptyAlphabet,
+                "At least one letter is needed to make words from".to_string());
+    }
+
+    for (i, diff) in cfg.game.difficulties.iter().enumerate() {
+        if diff.alphabet.as_ref().is_some_and(|a| a.letters.is_empty()) {
+            v.error(format!("game.difficulties[{}].alphabet.letters", i), ConfigIssueKind::EmptyAlphabet,
+                    "At least one letter is needed to make words from".to_string());
+        }
+    }
+
+    if cfg.game.word_sources.is_empty() {
+        v.error("game.word_sources".to_string(), ConfigIssueKind::NoWordSources,
+                "At least one word list is needed to pick keywords from".to_string());
+    }
+
+    for (i, multiplier) in cfg.game.streak.multipliers.iter().enumerate() {
+        v.check_positive(format!("game.streak.multipliers[{}]", i), *multiplier);
+    }
+
+    let rep = &cfg.game.reputation;
+    v.check_positive("game.reputation.max".to_string(), rep.max);
+    v.check_positive("game.reputation.low_order_period_scale".to_string(), rep.low_order_period_scale);
+    v.check_positive("game.reputation.high_order_period_scale".to_string(), rep.high_order_period_scale);
+    if rep.starting < 0.0 || rep.starting > rep.max {
+        v.error("game.reputation.starting".to_string(), ConfigIssueKind::ReputationOutOfRange { starting: rep.starting, max: rep.max },
+                format!("Expected from 0 to {}, got {}", rep.max, rep.starting));
+    }
+
+    if cfg.game.adaptive_words.strength < 0.0 {
+        v.error("game.adaptive_words.strength".to_string(), ConfigIssueKind::NegativeValue(cfg.game.adaptive_words.strength),
+                format!("Expected zero or more, got {}", cfg.game.adaptive_words.strength));
+    }
+
+    if cfg.game.word_score.rank_scale <= 0.0 {
+        v.error("game.word_score.rank_scale".to_string(), ConfigIssueKind::NonPositiveValue(cfg.game.word_score.rank_scale),
+                format!("Expected a positive value, got {}", cfg.game.word_score.rank_scale));
+    }
+
+    match cfg.game.keyword_entry.typo_penalty {
+        TypoPenalty::Money(amount) => v.check_positive("game.keyword_entry.typo_penalty.Money".to_string(), amount as f64),
+        TypoPenalty::Lockout(seconds) => v.check_positive("game.keyword_entry.typo_penalty.Lockout".to_string(), seconds),
+        TypoPenalty::None => {}
+    }
+
+    v.check_positive("game.order_bar.order_period".to_string(), cfg.game.order_bar.order_period);
+    v.check_positive("ui.order_bar.reject_seconds".to_string(), cfg.ui.order_bar.reject_seconds);
+    if cfg.game.order_bar.max_visible_orders == 0 {
+        v.error("game.order_bar.max_visible_orders".to_string(), ConfigIssueKind::NoVisibleOrders,
+                "At least one order must fit in the order bar".to_string());
+    }
+    v.check_positive("game.state.day_length".to_string(), cfg.game.state.day_length);
+    v.check_positive("game.state.money_down_sec".to_string(), cfg.game.state.money_down_sec);
+
+    if cfg.game.money.starting_money > cfg.game.money.max_money {
+        v.warning("game.money.starting_money".to_string(), ConfigIssueKind::StartingMoneyAboveMax,
+                  format!("Starting money {} is above the max of {}", cfg.game.money.starting_money, cfg.game.money.max_money));
+    }
+}
+
+fn check_store(v: &mut Validator, cfg: &OuterConfig) {
+    for (i, upgrs) in cfg.ui.store.upgrades.iter().enumerate() {
+        for (j, upgr) in upgrs.iter().enumerate() {
+            let path = format!("ui.store.upgrades[{}][{}]", i, j);
+            match upgr.action {
+                StoreUpgradeAction::UnlockCooker => {
+                    if !cfg.ui.preparation_area.cookers.iter().any(|c| c.base_image == upgr.img) {
+                        v.warning(format!("{}.img", path), ConfigIssueKind::NoCookerForUpgrade(upgr.img),
+                                  format!("No cooker uses {:?} as its base image", upgr.img));
+                    }
+                }
+                StoreUpgradeAction::IncreaseLimit => {
+                    if upgr.img != Image::MoneyBag {
+                        v.warning(format!("{}.img", path), ConfigIssueKind::UpgradeHasNoEffect(upgr.img),
+                                  format!("Only MoneyBag limits can be increased, not {:?}", upgr.img));
+                    }
+                }
+                StoreUpgradeAction::UnlockIngredient => {}
+            }
+        }
+    }
+}
+
+fn check_reachability(v: &mut Validator, cfg: &OuterConfig) {
+    let obtainable = obtainable_ingredients(cfg, true);
+    let at_start = obtainable_ingredients(cfg, false);
+
+    for (i, cooker) in cfg.ui.preparation_area.cookers.iter().enumerate() {
+        let path = format!("ui.preparation_area.cookers[{}]", i);
+
+        let can_unlock = cooker.num_unlocked > 0 ||
+            cfg.ui.store.upgrades.iter().flatten().any(|u| u.action == StoreUpgradeAction::UnlockCooker && u.img == cooker.base_image);
+        if !can_unlock {
+            v.warning(path.clone(), ConfigIssueKind::CookerNeverUnlocked(cooker.base_image),
+                      format!("{:?} is never unlocked, so its recipes can't be used", cooker.base_image));
+            continue;
+        }
+
+        for (j, recipe) in cooker.recipes.iter().enumerate() {
+            for (k, ing) in recipe.inputs.iter().enumerate() {
+                if !obtainable.contains(ing) {
+                    v.warning(format!("{}.recipes[{}].inputs[{}]", path, j, k), ConfigIssueKind::RecipeInputUnavailable(*ing),
+                              format!("No ingredient, upgrade or recipe provides {:?}", ing));
+                }
+            }
+        }
+    }
+
+    if cfg.ui.order_bar.orders.is_empty() {
+        v.error("ui.order_bar.orders".to_string(), ConfigIssueKind::NoOrders, "There must be at least one order".to_string());
+        return;
+    }
+
+    for (i, order) in cfg.ui.order_bar.orders.iter().enumerate() {
+        for (j, ing) in order.ings.iter().enumerate() {
+            if ing.chance >= 1.0 && !obtainable.contains(&ing.ing) {
+                v.warning(format!("ui.order_bar.orders[{}].ings[{}].ing", i, j), ConfigIssueKind::OrderIngredientUnavailable(ing.ing),
+                          format!("No ingredient, upgrade or recipe provides {:?}, so this order is never made", ing.ing));
+            }
+        }
+    }
+
+    let any_at_start = cfg.ui.order_bar.orders.iter()
+        .any(|order| order.ings.iter().all(|ing| ing.chance < 1.0 || at_start.contains(&ing.ing)));
+    if !any_at_start {
+        v.error("ui.order_bar.orders".to_string(), ConfigIssueKind::NoOrdersAtStart,
+                "No order can be made with the starting ingredients and cookers".to_string());
+    }
+}
+
+/// Check the specified 'cfg' for problems, returning all errors and warnings found
+pub fn validate_config(cfg: &OuterConfig) -> Vec<ConfigIssue> {
+    let mut v = Validator {
+        issues: Vec::new(),
+    };
+
+    check_images(&mut v, cfg);
+    check_values(&mut v, cfg);
+    check_store(&mut v, cfg);
+    check_reachability(&mut v, cfg);
+
+    v.issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_config;
+
+    // Return the issue found at the specified 'path', if any
+    fn issue_at(cfg: &OuterConfig, path: &str) -> Option<ConfigIssue> {
+        validate_config(cfg).into_iter().find(|issue| issue.path == path)
+    }
+
+    #[test]
+    fn page_config_has_no_errors() {
+        let issues = validate_config(&test_config());
+        assert!(!has_errors(&issues), "{}", describe_errors(&issues));
+    }
+
+    #[test]
+    fn zero_grid_width() {
+        let mut cfg = test_config();
+        cfg.ui.ingredient_area.grid_width = 0;
+
+        let issue = issue_at(&cfg, "ui.ingredient_area.grid_width").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.kind, ConfigIssueKind::ZeroGridWidth);
+    }
+
+    #[test]
+    fn too_many_unlocked() {
+        let mut cfg = test_config();
+        let instances = cfg.ui.preparation_area.cookers[0].instances.len();
+        cfg.ui.preparation_area.cookers[0].num_unlocked = instances as i32 + 1;
+
+        let issue = issue_at(&cfg, "ui.preparation_area.cookers[0].num_unlocked").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.kind, ConfigIssueKind::UnlockedCountOutOfRange { num_unlocked: instances as i32 + 1, instances });
+    }
+
+    #[test]
+    fn unknown_difficulty() {
+        let mut cfg = test_config();
+        cfg.game.difficulty = cfg.game.difficulties.len();
+
+        let issue = issue_at(&cfg, "game.difficulty").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+    }
+
+    #[test]
+    fn empty_score_range() {
+        let mut cfg = test_config();
+        let level = &mut cfg.game.difficulties[1].word_levels[0];
+        level.max_score = level.min_score;
+
+        let issue = issue_at(&cfg, "game.difficulties[1].word_levels[0]").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert!(matches!(issue.kind, ConfigIssueKind::EmptyScoreRange { .. }));
+    }
+
+    #[test]
+    fn non_positive_day_length() {
+        let mut cfg = test_config();
+        cfg.game.state.day_length = 0.0;
+
+        let issue = issue_at(&cfg, "game.state.day_length").unwrap();
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(issue.kind, ConfigIssueKind::NonPositiveValue(0.0));
+    }
+
+    #[test]
+    fn zero_patience() {
+        let mut cfg = test_config();
+        cfg.ui.order_bar.orders[0].patience_seconds = 0.0;
+
+        let issue = issue_at(&cfg, "ui.order_bar.orders[0].patience_seconds").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.kind, ConfigIssueKind::NonPositiveValue(0.0));
+    }
+
+    #[test]
+    fn starting_money_above_max() {
+        let mut cfg = test_config();
+        cfg.game.money.starting_money = cfg.game.money.max_money + 1;
+
+        let issue = issue_at(&cfg, "game.money.starting_money").unwrap();
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(issue.kind, ConfigIssueKind::StartingMoneyAboveMax);
+    }
+
+    #[test]
+    fn no_orders() {
+        let mut cfg = test_config();
+        cfg.ui.order_bar.orders.clear();
+
+        let issue = issue_at(&cfg, "ui.order_bar.orders").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.kind, ConfigIssueKind::NoOrders);
+    }
+
+    #[test]
+    fn no_orders_at_start() {
+        // Nothing the orders need is available until it's bought
+        let mut cfg = test_config();
+        cfg.game.ingredient_area.ingredients.clear();
+
+        let issue = issue_at(&cfg, "ui.order_bar.orders").unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.kind, ConfigIssueKind::NoOrdersAtStart);
+    }
+
+    #[test]
+    fn order_ingredient_unavailable() {
+        let mut cfg = test_config();
+        let ing = cfg.ui.order_bar.orders[0].ings[0].ing;
+        cfg.game.ingredient_area.ingredients.retain(|i| *i != ing);
+        for upgr in cfg.ui.store.upgrades.iter_mut().flatten() {
+            if upgr.img == ing {
+                upgr.action = StoreUpgradeAction::IncreaseLimit;
+            }
+        }
+        cfg.ui.preparation_area.cookers.iter_mut().for_each(|c| c.recipes.retain(|r| !r.outputs.contains(&ing)));
+        cfg.ui.order_bar.orders[0].ings[0].chance = 1.0;
+
+        let issue = issue_at(&cfg, "ui.order_bar.orders[0].ings[0].ing").unwrap();
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(issue.kind, ConfigIssueKind::OrderIngredientUnavailable(ing));
+    }
+
+    #[test]
+    fn limit_upgrade_without_effect() {
+        let mut cfg = test_config();
+        let upgr = &mut cfg.ui.store.upgrades[0][0];
+        upgr.action = StoreUpgradeAction::IncreaseLimit;
+        let img = upgr.img;
+
+        let issue = issue_at(&cfg, "ui.store.upgrades[0][0].img").unwrap();
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(issue.kind, ConfigIssueKind::UpgradeHasNoEffect(img));
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Serialize,Deserialize};
+
+/// Weights for scoring how hard a word is to type.  Higher scores are harder.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WordScoreConfig {
+    pub rank_weight: f64,           // added per 'rank_scale' words more common than this one
+    pub rank_scale: f64,
+    pub length_weight: f64,         // added per character beyond 'base_length'
+    pub base_length: usize,
+    pub rare_letters: String,       // letters that are awkward to reach, like "jqxz"
+    pub rare_letter_weight: f64,    // added per rare letter in the word
+    pub awkward_bigrams: Vec<String>, // letter pairs that are awkward to type, like "xc"
+    pub awkward_bigram_weight: f64, // added per awkward pair in the word
+    pub same_finger_weight: f64,    // added per pair of different letters typed with the same finger
+    pub row_jump_weight: f64,       // added per pair of letters on the top and bottom rows
+    pub shifted_weight: f64,        // added per character typed with Shift, like "A" or "<"
+}
+
+/// A range of word scores, and how often new words come from it
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WordLevelConfig {
+    pub min_score: f64,
+    pub max_score: f64,       // exclusive
+    pub weight: f64,          // how likely new words are to come from this level
+    pub letter_pairs: bool,   // use every 2-letter combination of letters, rather than real words
+    pub phrases: Vec<String>, // if not empty, use these multi-word phrases rather than single words
+}
+
+impl WordLevelConfig {
+    pub fn contains(&self, score: f64) -> bool {
+        score >= self.min_score && score < self.max_score
+    }
+}
+
+// QWERTY rows, from top to bottom
+const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+// Symbols typed with Shift on a US QWERTY keyboard
+const SHIFTED_SYMBOLS: &str = "~!@#$%^&*()_+{}|:\"<>?";
+
+// Return the row and finger (0 for the left pinky to 7 for the right pinky) used to type 'c'
+// with touch typing on a QWERTY keyboard
+fn key_position(c: char) -> Option<(usize, usize)> {
+    for (row, keys) in ROWS.iter().enumerate() {
+        if let Some(col) = keys.find(c.to_ascii_lowercase()) {
+            let finger = match col {
+                0..=2 => col,
+                3 | 4 => 3,
+                5 | 6 => 4,
+                _ => col - 2,
+            };
+            return Some((row, finger));
+        }
+    }
+
+    None
+}
+
+/// Score how hard the specified 'word' is to type using the specified 'cfg'.  'rank' is how many
+/// words are more common than it.
+pub fn score_word(word: &str, rank: usize, cfg: &WordScoreConfig) -> f64 {
+    let mut score = cfg.rank_weight * rank as f64 / cfg.rank_scale;
+
+    let len = word.chars().count();
+    if len > cfg.base_length {
+        score += cfg.length_weight * (len - cfg.base_length) as f64;
+    }
+
+    score += cfg.rare_letter_weight * word.chars().filter(|c| cfg.rare_letters.contains(*c)).count() as f64;
+    score += cfg.shifted_weight * word.chars().filter(|c| c.is_uppercase() || SHIFTED_SYMBOLS.contains(*c)).count() as f64;
+
+    let chars: Vec<char> = word.chars().collect();
+    for pair in chars.windows(2) {
+        let bigram: String = pair.iter().collect();
+        if cfg.awkward_bigrams.contains(&bigram) {
+            score += cfg.awkward_bigram_weight;
+        }
+
+        if let (Some((row0, finger0)), Some((row1, finger1))) = (key_position(pair[0]), key_position(pair[1])) {
+            if finger0 == finger1 && pair[0] != pair[1] {
+                score += cfg.same_finger_weight;
+            }
+            if row0.abs_diff(row1) == 2 {
+                score += cfg.row_jump_weight;
+            }
+        }
+    }
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A config where only the weights set afterwards count
+    fn zero_config() -> WordScoreConfig {
+        WordScoreConfig {
+            rank_weight: 0.0,
+            rank_scale: 1.0,
+            length_weight: 0.0,
+            base_length: 4,
+            rare_letters: "jqxz".to_string(),
+            rare_letter_weight: 0.0,
+            awkward_bigrams: vec!["ce".to_string()],
+            awkward_bigram_weight: 0.0,
+            same_finger_weight: 0.0,
+            row_jump_weight: 0.0,
+            shifted_weight: 0.0,
+        }
+    }
+
+    #[test]
+    fn rank() {
+        let cfg = WordScoreConfig { rank_weight: 1.0, rank_scale: 100.0, ..zero_config() };
+        assert_eq!(score_word("dog", 0, &cfg), 0.0);
+        assert_eq!(score_word("dog", 250, &cfg), 2.5);
+    }
+
+    #[test]
+    fn length_beyond_base() {
+        let cfg = WordScoreConfig { length_weight: 1.0, ..zero_config() };
+        assert_eq!(score_word("dogs", 0, &cfg), 0.0);
+        assert_eq!(score_word("doggies", 0, &cfg), 3.0);
+        assert_eq!(score_word("façade", 0, &cfg), 2.0);
+    }
+
+    #[test]
+    fn rare_letters() {
+        let cfg = WordScoreConfig { rare_letter_weight: 1.0, ..zero_config() };
+        assert_eq!(score_word("jazz", 0, &cfg), 3.0);
+    }
+
+    #[test]
+    fn awkward_bigrams() {
+        let cfg = WordScoreConfig { awkward_bigram_weight: 1.0, ..zero_config() };
+        assert_eq!(score_word("nice", 0, &cfg), 1.0);
+        assert_eq!(score_word("cent", 0, &cfg), 1.0);
+        assert_eq!(score_word("ec", 0, &cfg), 0.0);
+    }
+
+    #[test]
+    fn same_finger() {
+        // 'd', 'e' and 'c' are all typed with the left middle finger, but a repeated letter isn't
+        // a jump between keys
+        let cfg = WordScoreConfig { same_finger_weight: 1.0, ..zero_config() };
+        assert_eq!(score_word("dec", 0, &cfg), 2.0);
+        assert_eq!(score_word("dd", 0, &cfg), 0.0);
+        assert_eq!(score_word("as", 0, &cfg), 0.0);
+    }
+
+    #[test]
+    fn row_jumps() {
+        let cfg = WordScoreConfig { row_jump_weight: 1.0, ..zero_config() };
+        assert_eq!(score_word("qz", 0, &cfg), 1.0);
+        assert_eq!(score_word("qa", 0, &cfg), 0.0);
+    }
+
+    #[test]
+    fn shifted() {
+        let cfg = WordScoreConfig { shifted_weight: 1.0, ..zero_config() };
+        assert_eq!(score_word("Foo<T>", 0, &cfg), 4.0);
+        assert_eq!(score_word("foo", 0, &cfg), 0.0);
+    }
+
+    #[test]
+    fn level_range() {
+        let level = WordLevelConfig { min_score: 1.0, max_score: 2.0, weight: 1.0, letter_pairs: false, phrases: Vec::new() };
+        assert!(level.contains(1.0));
+        assert!(level.contains(1.5));
+        assert!(!level.contains(2.0));
+        assert!(!level.contains(0.5));
+    }
+}
@@ -0,0 +1,247 @@
+use serde::{Serialize,Deserialize};
+
+use std::collections::HashMap;
+
+/// How a word list's text is laid out
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WordListFormat {
+    Plain,     // a word per line
+    Frequency, // a 'word count' pair per line, most frequent first
+    Json,      // an array of '{"word": ..., "tags": [...]}' objects, most common first
+}
+
+/// A word list to load keywords from
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WordSourceConfig {
+    pub name: String,      // key of the list's text in the word lists given to the game
+    pub url: String,       // where the page loads the list from
+    pub format: WordListFormat,
+    pub tags: Vec<String>, // added to every word in the list
+}
+
+/// The tags of words each area picks its keywords from.  An empty list means any word.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WordPoolsConfig {
+    pub orders: Vec<String>,
+    pub ingredients: Vec<String>,
+    pub cookers: Vec<String>,
+    pub store: Vec<String>,
+    pub state: Vec<String>,
+}
+
+/// Which area a new keyword is for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WordPool {
+    Orders,
+    Ingredients,
+    Cookers,
+    Store,
+    State,
+}
+
+impl WordPoolsConfig {
+    pub fn tags(&self, pool: WordPool) -> &Vec<String> {
+        match pool {
+            WordPool::Orders => &self.orders,
+            WordPool::Ingredients => &self.ingredients,
+            WordPool::Cookers => &self.cookers,
+            WordPool::Store => &self.store,
+            WordPool::State => &self.state,
+        }
+    }
+}
+
+/// A word read from a word list
+#[derive(Clone, Debug)]
+pub struct SourceWord {
+    pub word: String,
+    pub tags: Vec<String>,
+    pub rank: usize, // how many words are more common than this one in its list
+}
+
+/// Something that words can be read from
+pub trait WordSource {
+    /// Return the words, most common first
+    fn words(&self) -> Result<Vec<SourceWord>, String>;
+}
+
+pub struct PlainList<'a>(pub &'a str);
+
+impl WordSource for PlainList<'_> {
+    fn words(&self) -> Result<Vec<SourceWord>, String> {
+        Ok(self.0.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .enumerate()
+            .map(|(rank, word)| SourceWord { word: word.to_string(), tags: Vec::new(), rank })
+            .collect())
+    }
+}
+
+pub struct FrequencyList<'a>(pub &'a str);
+
+impl WordSource for FrequencyList<'_> {
+    fn words(&self) -> Result<Vec<SourceWord>, String> {
+        Ok(self.0.lines()
+            .filter_map(|line| line.split(' ').next())
+            .filter(|word| !word.is_empty())
+            .enumerate()
+            .map(|(rank, word)| SourceWord { word: word.to_string(), tags: Vec::new(), rank })
+            .collect())
+    }
+}
+
+pub struct JsonList<'a>(pub &'a str);
+
+#[derive(Deserialize)]
+struct JsonWord {
+    word: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl WordSource for JsonList<'_> {
+    fn words(&self) -> Result<Vec<SourceWord>, String> {
+        let words: Vec<JsonWord> = serde_json::from_str(self.0).map_err(|e| format!("Failed parsing word list: {}", e))?;
+
+        Ok(words.into_iter()
+            .enumerate()
+            .map(|(rank, w)| SourceWord { word: w.word, tags: w.tags, rank })
+            .collect())
+    }
+}
+
+/// Return a 'WordSource' reading the specified 'text' in the specified 'format'
+pub fn new_word_source(format: WordListFormat, text: &str) -> Box<dyn WordSource + '_> {
+    match format {
+        WordListFormat::Plain => Box::new(PlainList(text)),
+        WordListFormat::Frequency => Box::new(FrequencyList(text)),
+        WordListFormat::Json => Box::new(JsonList(text)),
+    }
+}
+
+/// Load the words of all the specified 'sources', whose texts are in 'lists' by name.  A word in
+/// several lists appears once, with all its tags and its best rank.
+pub fn load_words(sources: &[WordSourceConfig], lists: &HashMap<String, String>) -> Result<Vec<SourceWord>, String> {
+    let mut ret: Vec<SourceWord> = Vec::new();
+    let mut indices: HashMap<String, usize> = HashMap::new();
+
+    for source in sources.iter() {
+        let text = lists.get(&source.name)
+            .ok_or_else(|| format!("No text was given for word list '{}'", source.name))?;
+        let words = new_word_source(source.format, text).words()
+            .map_err(|e| format!("{} ({})", e, source.name))?;
+
+        for mut word in words.into_iter() {
+            word.tags.extend(source.tags.iter().cloned());
+
+            match indices.get(&word.word) {
+                Some(idx) => {
+                    let existing = &mut ret[*idx];
+                    existing.rank = existing.rank.min(word.rank);
+                    for tag in word.tags.into_iter() {
+                        if !existing.tags.contains(&tag) {
+                            existing.tags.push(tag);
+                        }
+                    }
+                }
+                None => {
+                    indices.insert(word.word.clone(), ret.len());
+                    ret.push(word);
+                }
+            }
+        }
+    }
+
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, format: WordListFormat, tags: &[&str]) -> WordSourceConfig {
+        WordSourceConfig {
+            name: name.to_string(),
+            url: String::new(),
+            format,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn lists(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(name, text)| (name.to_string(), text.to_string())).collect()
+    }
+
+    #[test]
+    fn plain() {
+        let words = PlainList("one\n\n  two \nthree\n").words().unwrap();
+        let found: Vec<(&str, usize)> = words.iter().map(|w| (w.word.as_str(), w.rank)).collect();
+        assert_eq!(found, vec![("one", 0), ("two", 1), ("three", 2)]);
+    }
+
+    #[test]
+    fn frequency() {
+        let words = FrequencyList("the 500\nof 300\n\nand 200").words().unwrap();
+        let found: Vec<(&str, usize)> = words.iter().map(|w| (w.word.as_str(), w.rank)).collect();
+        assert_eq!(found, vec![("the", 0), ("of", 1), ("and", 2)]);
+    }
+
+    #[test]
+    fn json() {
+        let words = JsonList(r#"[{"word": "egg", "tags": ["food"]}, {"word": "pan"}]"#).words().unwrap();
+        assert_eq!(words[0].word, "egg");
+        assert_eq!(words[0].tags, vec!["food"]);
+        assert_eq!(words[1].rank, 1);
+        assert!(words[1].tags.is_empty());
+
+        assert!(JsonList("[{\"tags\": []}]").words().is_err());
+    }
+
+    #[test]
+    fn sources_merged() {
+        let sources = vec![
+            source("general", WordListFormat::Frequency, &["general"]),
+            source("food", WordListFormat::Json, &[]),
+        ];
+        let texts = lists(&[
+            ("general", "the 9\negg 5\nham 3"),
+            ("food", r#"[{"word": "ham", "tags": ["food"]}, {"word": "egg", "tags": ["food"]}]"#),
+        ]);
+
+        let words = load_words(&sources, &texts).unwrap();
+        assert_eq!(words.len(), 3);
+
+        // Each word keeps its best rank, and the tags from every list it's in
+        let ham = words.iter().find(|w| w.word == "ham").unwrap();
+        assert_eq!(ham.rank, 0);
+        assert_eq!(ham.tags, vec!["general", "food"]);
+        let the = words.iter().find(|w| w.word == "the").unwrap();
+        assert_eq!(the.tags, vec!["general"]);
+    }
+
+    #[test]
+    fn missing_list() {
+        let sources = vec![source("general", WordListFormat::Plain, &[])];
+        assert!(load_words(&sources, &HashMap::new()).unwrap_err().contains("general"));
+    }
+
+    #[test]
+    fn bad_list_named() {
+        let sources = vec![source("food", WordListFormat::Json, &[])];
+        assert!(load_words(&sources, &lists(&[("food", "not json")])).unwrap_err().contains("food"));
+    }
+
+    #[test]
+    fn pool_tags() {
+        let pools = WordPoolsConfig {
+            orders: vec!["general".to_string()],
+            ingredients: vec!["food".to_string()],
+            cookers: Vec::new(),
+            store: Vec::new(),
+            state: Vec::new(),
+        };
+        assert_eq!(pools.tags(WordPool::Ingredients), &vec!["food".to_string()]);
+        assert!(pools.tags(WordPool::State).is_empty());
+    }
+}
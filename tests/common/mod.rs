@@ -0,0 +1,87 @@
+// Setup shared by the headless tests.  Each test file only uses some of it.
+#![allow(dead_code)]
+
+use res_type_rant::{Game, GameEvent, OuterConfig};
+use serde_json::Value;
+
+use std::collections::HashMap;
+
+/// Time simulated by each call to 'Game::step', as if running at 30 FPS like the page does
+pub const FRAME: f64 = 1.0 / 30.0;
+
+/// Return the config made by 'genConfig' in config.js, as JSON, for tests to adjust before
+/// parsing.  'data/config.json' is regenerated from the root of the repo with:
+///
+///     node --input-type=module -e "import {genConfig} from './config.js'; console.log(JSON.stringify(genConfig(), null, 2))" > tests/data/config.json
+pub fn config_json() -> Value {
+    serde_json::from_str(include_str!("../data/config.json")).expect("tests/data/config.json should be valid JSON")
+}
+
+pub fn config() -> OuterConfig {
+    parse_config(config_json())
+}
+
+pub fn parse_config(cfg: Value) -> OuterConfig {
+    serde_json::from_value(cfg).expect("config should parse")
+}
+
+/// Return the text of each of the config's word sources.  The general list, which is downloaded
+/// by the page, is replaced by every 3 letter word, so there's always plenty to pick from.
+pub fn word_lists() -> HashMap<String, String> {
+    let letters = || 'a'..='z';
+    let mut general = String::new();
+    for a in letters() {
+        for b in letters() {
+            for c in letters() {
+                general.push_str(&format!("{}{}{} 1\n", a, b, c));
+            }
+        }
+    }
+
+    let mut lists = HashMap::new();
+    lists.insert("general".to_string(), general);
+    lists.insert("food".to_string(), include_str!("../../words/food.json").to_string());
+    lists.insert("code".to_string(), include_str!("../../words/code.txt").to_string());
+    lists
+}
+
+pub fn new_game(cfg: OuterConfig, seed: u32) -> Game {
+    Game::new(cfg, seed, &word_lists(), &String::new()).expect("game should start")
+}
+
+/// Step 'game' a frame at a time, for up to 'max_seconds', until it emits an event matching
+/// 'pred', and return that event
+pub fn step_until(game: &mut Game, max_seconds: f64, pred: impl Fn(&GameEvent) -> bool) -> Option<GameEvent> {
+    let mut elapsed = 0.0;
+    while elapsed < max_seconds {
+        game.step(FRAME);
+        elapsed += FRAME;
+
+        if let Some(event) = game.take_events().into_iter().find(|e| pred(e)) {
+            return Some(event);
+        }
+    }
+
+    None
+}
+
+/// Step 'game' like 'step_until', and return how many seconds it took for an event matching
+/// 'pred' to be emitted
+pub fn seconds_until(game: &mut Game, max_seconds: f64, pred: impl Fn(&GameEvent) -> bool) -> Option<f64> {
+    let mut elapsed = 0.0;
+    while elapsed < max_seconds {
+        game.step(FRAME);
+        elapsed += FRAME;
+
+        if game.take_events().iter().any(&pred) {
+            return Some(elapsed);
+        }
+    }
+
+    None
+}
+
+/// Return the keywords on screen, other than 'exclude'
+pub fn keywords_except(game: &Game, exclude: &[&str]) -> Vec<String> {
+    game.keywords().into_iter().filter(|kw| !exclude.contains(&kw.as_str())).collect()
+}
@@ -0,0 +1,34 @@
+// The same seed and inputs must always produce the same game
+mod common;
+
+use common::FRAME;
+
+// Play for a while with the specified 'seed', typing everything on screen every half second, and
+// return the keywords seen and events that happened
+fn play(seed: u32) -> (Vec<Vec<String>>, Vec<String>) {
+    let mut game = common::new_game(common::config(), seed);
+
+    let mut keywords = Vec::new();
+    let mut events = Vec::new();
+    for frame in 0..3000 {
+        game.step(FRAME);
+        if frame % 15 == 0 {
+            let shown = game.keywords();
+            game.enter_command(&shown.join(" "));
+            keywords.push(shown);
+        }
+        events.extend(game.take_events().iter().map(|e| format!("{:?}", e)));
+    }
+
+    (keywords, events)
+}
+
+#[test]
+fn same_seed_same_game() {
+    assert_eq!(play(7), play(7));
+}
+
+#[test]
+fn different_seed_different_words() {
+    assert_ne!(play(7).0, play(8).0);
+}
@@ -0,0 +1,266 @@
+// Drives a whole day headless: serving an order that needs cooking, closing up, and shopping
+mod common;
+
+use common::{keywords_except, step_until, FRAME};
+use res_type_rant::{Game, GameEvent, OuterConfig};
+use serde_json::json;
+
+// A restaurant with one raw ingredient, one pan to cook it in, and one order that needs it
+// cooked.  Customers never run out of patience or lower their price, and everything in the store costs $1.
+fn cooking_config() -> OuterConfig {
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(1);
+    cfg["game"]["ingredient_area"]["ingredients"] = json!(["RawPatty"]);
+    cfg["ui"]["preparation_area"]["cookers"][0]["num_unlocked"] = json!(1);
+
+    let mut order = cfg["ui"]["order_bar"]["orders"][0].clone();
+    order["ings"] = json!([{ "ing": "CookedPatty", "chance": 1.0, "price": 10 }]);
+    order["patience_seconds"] = json!(1000.0);
+    order["depreciation_seconds"] = json!(1000.0);
+    cfg["ui"]["order_bar"]["orders"] = json!([order]);
+
+    for upgrs in cfg["ui"]["store"]["upgrades"].as_array_mut().unwrap() {
+        for upgr in upgrs.as_array_mut().unwrap() {
+            upgr["cost"] = json!(1);
+        }
+    }
+
+    common::parse_config(cfg)
+}
+
+#[test]
+fn serve_cooked_order_and_shop() {
+    let mut game = common::new_game(cooking_config(), 1);
+
+    let order = match step_until(&mut game, 30.0, |e| matches!(e, GameEvent::OrderCreated { .. })) {
+        Some(GameEvent::OrderCreated { keyword }) => keyword,
+        e => panic!("Expected an order, got {:?}", e),
+    };
+
+    // Send the raw patty to the pan
+    game.enter_command(&keywords_except(&game, &[&order]).join(" "));
+    assert!(step_until(&mut game, 5.0, |e| matches!(e, GameEvent::CookingStarted { .. })).is_some());
+    assert!(step_until(&mut game, 30.0, |e| matches!(e, GameEvent::CookingDone { .. })).is_some());
+
+    // Send the cooked patty to the order.  This also picks up another raw patty, which the order
+    // doesn't want.
+    let money = game.money();
+    let others = keywords_except(&game, &[&order]);
+    game.enter_command(&format!("{} {}", others.join(" "), order));
+    match step_until(&mut game, 10.0, |e| matches!(e, GameEvent::OrderServed { .. })) {
+        Some(GameEvent::OrderServed { price, .. }) => assert_eq!(price, 10),
+        e => panic!("Expected the order to be served, got {:?}", e),
+    }
+    assert!(game.money() > money);
+
+    // Wait for closing time, when the sign shows up
+    let mut sign = None;
+    let mut elapsed = 0.0;
+    while sign.is_none() && elapsed < 200.0 {
+        let before = game.keywords();
+        game.step(FRAME);
+        elapsed += FRAME;
+        if game.take_events().contains(&GameEvent::DayEnded) {
+            sign = game.keywords().into_iter().find(|kw| !before.contains(kw));
+        }
+    }
+    let sign = sign.expect("The day should end, showing the sign");
+
+    // Close, go past the summary to the store, and buy whatever's there
+    game.enter_command(&sign);
+    let sign = game.keywords().pop().expect("The summary should show the sign");
+    game.enter_command(&sign);
+    assert!(game.in_store());
+
+    let money = game.money();
+    game.enter_command(&keywords_except(&game, &[&sign]).join(" "));
+    let purchases = game.take_events().into_iter().filter(|e| matches!(e, GameEvent::UpgradePurchased { .. })).count();
+    assert!(purchases > 0);
+    assert_eq!(game.money(), money - purchases as i32);
+}
+
+// A short day with one order that only needs lettuce, and a customer who never leaves
+fn lettuce_config() -> OuterConfig {
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(1);
+    cfg["game"]["state"]["day_length"] = json!(12.0);
+
+    let mut order = cfg["ui"]["order_bar"]["orders"][0].clone();
+    order["ings"] = json!([{ "ing": "LettuceLeaf", "chance": 1.0, "price": 10 }]);
+    order["patience_seconds"] = json!(1000.0);
+    order["depreciation_seconds"] = json!(1000.0);
+    cfg["ui"]["order_bar"]["orders"] = json!([order]);
+
+    common::parse_config(cfg)
+}
+
+// Wait for the day to end and deliver the order after it, so it's served after closing time.  If
+// 'close_after' is given, close up that many frames after delivering.  Return the events from
+// delivering on, and the number of frames it took.
+fn serve_after_closing(close_after: Option<usize>) -> (Vec<GameEvent>, usize) {
+    let mut game = common::new_game(lettuce_config(), 2);
+
+    let order = match step_until(&mut game, 30.0, |e| matches!(e, GameEvent::OrderCreated { .. })) {
+        Some(GameEvent::OrderCreated { keyword }) => keyword,
+        e => panic!("Expected an order, got {:?}", e),
+    };
+
+    let mut sign = None;
+    while sign.is_none() {
+        let before = game.keywords();
+        game.step(FRAME);
+        if game.take_events().contains(&GameEvent::DayEnded) {
+            sign = game.keywords().into_iter().find(|kw| !before.contains(kw));
+        }
+    }
+    let sign = sign.unwrap();
+
+    let lettuce = keywords_except(&game, &[&order, &sign]);
+    game.enter_command(&format!("{} {}", lettuce[0], order));
+
+    let mut events = Vec::new();
+    for frame in 0..300 {
+        if Some(frame) == close_after {
+            game.enter_command(&sign);
+        }
+
+        game.step(FRAME);
+        events.extend(game.take_events());
+        if close_after.is_none() && events.iter().any(|e| matches!(e, GameEvent::OrderServed { .. })) {
+            return (events, frame);
+        }
+    }
+
+    (events, 300)
+}
+
+#[test]
+fn closing_pays_for_orders_being_served() {
+    let (_, serve_frames) = serve_after_closing(None);
+    assert!(serve_frames < 300, "The order should be served");
+
+    // Close the frame before the order would finish leaving with its food
+    let (events, _) = serve_after_closing(Some(serve_frames));
+    assert!(events.iter().any(|e| matches!(e, GameEvent::OrderServed { price: 10, .. })), "{:?}", events);
+
+    // Only customers who came in later are still waiting
+    let created = events.iter().filter(|e| matches!(e, GameEvent::OrderCreated { .. })).count();
+    let missed = events.iter().filter(|e| **e == GameEvent::OrderMissed).count();
+    assert_eq!(missed, created, "{:?}", events);
+}
+
+#[test]
+fn patience_starts_once_order_arrives() {
+    let mut cfg = common::config_json();
+    let mut order = cfg["ui"]["order_bar"]["orders"][0].clone();
+    order["ings"] = json!([{ "ing": "LettuceLeaf", "chance": 1.0, "price": 10 }]);
+    order["patience_seconds"] = json!(2.0);
+    cfg["ui"]["order_bar"]["orders"] = json!([order]);
+    let mut game = common::new_game(common::parse_config(cfg), 2);
+
+    // The customer waits out their patience after sliding into the bar
+    match step_until(&mut game, 30.0, |e| matches!(e, GameEvent::OrderExpired { .. })) {
+        Some(GameEvent::OrderExpired { wait_time, .. }) => assert!(wait_time > 2.5, "Left after {} seconds", wait_time),
+        e => panic!("Expected the customer to leave, got {:?}", e),
+    }
+}
+
+#[test]
+fn reputation_change_keeps_customer_arrival_time() {
+    let mut cfg = common::config_json();
+    let mut order = cfg["ui"]["order_bar"]["orders"][0].clone();
+    order["ings"] = json!([{ "ing": "LettuceLeaf", "chance": 1.0, "price": 10 }]);
+    order["patience_seconds"] = json!(1.0);
+    cfg["ui"]["order_bar"]["orders"] = json!([order]);
+    // The customer leaves slowly, so the next one is on the way before the order is gone
+    cfg["ui"]["order_bar"]["leave_offset"] = json!({ "x": 0, "y": 10000 });
+    cfg["game"]["order_bar"]["order_period"] = json!(10.0);
+    cfg["game"]["reputation"]["expired_loss"] = json!(5.0);
+    let cfg = common::parse_config(cfg);
+    let expected = 10.0 * cfg.game.reputation.order_period_scale(cfg.game.reputation.starting);
+    let mut game = common::new_game(cfg, 2);
+
+    assert!(step_until(&mut game, 30.0, |e| matches!(e, GameEvent::OrderCreated { .. })).is_some());
+
+    // The first customer leaving loses all the reputation, but the next customer was already
+    // coming at the old one's rate
+    let mut elapsed = 0.0;
+    let mut expired = false;
+    loop {
+        game.step(FRAME);
+        elapsed += FRAME;
+        let events = game.take_events();
+        expired |= events.iter().any(|e| matches!(e, GameEvent::OrderExpired { .. }));
+        if events.iter().any(|e| matches!(e, GameEvent::OrderCreated { .. })) {
+            break;
+        }
+        assert!(elapsed < 30.0, "No second order");
+    }
+
+    assert!(expired);
+    assert!((elapsed - expected).abs() < 2.0 * FRAME, "Next order after {} seconds, expected {}", elapsed, expected);
+}
+
+#[test]
+fn config_change_keeps_customer_arrival_time() {
+    let mut cfg = lettuce_config();
+    cfg.game.state.day_length = 90.0;
+    cfg.game.order_bar.order_period = 10.0;
+    let expected = 10.0 * cfg.game.reputation.order_period_scale(cfg.game.reputation.starting);
+    let mut game = common::new_game(cfg.clone(), 2);
+
+    let is_order = |e: &GameEvent| matches!(e, GameEvent::OrderCreated { .. });
+    assert!(step_until(&mut game, 30.0, is_order).is_some());
+
+    // Editing the config while the next customer is on their way doesn't change when they arrive
+    let waited = common::seconds_until(&mut game, 3.0, is_order);
+    assert!(waited.is_none());
+    cfg.ui.order_bar.order_margin += 5.0;
+    game.change_config(&cfg).unwrap();
+
+    let elapsed = 3.0 + common::seconds_until(&mut game, 30.0, is_order).expect("No second order");
+    assert!((elapsed - expected).abs() < 2.0 * FRAME, "Next order after {} seconds, expected {}", elapsed, expected);
+}
+
+#[test]
+fn sold_out_upgrade_keyword_ignored() {
+    // Few enough words for the store that typing all of them is sure to include the hidden
+    // keyword a sold out upgrade is left with
+    let mut lists = common::word_lists();
+    let general: Vec<String> = lists["general"].lines().take(80).map(String::from).collect();
+    lists.insert("general".to_string(), general.join("\n"));
+
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(1);
+    cfg["game"]["difficulties"][1]["word_levels"][1]["weight"] = json!(0.0);
+    cfg["game"]["word_pools"]["store"] = json!(["general"]);
+    cfg["game"]["state"]["day_length"] = json!(5.0);
+    cfg["game"]["money"]["starting_money"] = json!(100);
+    let mut game = Game::new(common::parse_config(cfg), 1, &lists, &String::new()).unwrap();
+
+    let mut sign = None;
+    while sign.is_none() {
+        let before = game.keywords();
+        game.step(FRAME);
+        if game.take_events().contains(&GameEvent::DayEnded) {
+            sign = game.keywords().into_iter().find(|kw| !before.contains(kw));
+        }
+    }
+    let sign = sign.unwrap();
+    game.enter_command(&sign);
+    let sign = game.keywords().pop().unwrap();
+    game.enter_command(&sign);
+    assert!(game.in_store());
+
+    // The sign to leave comes first, then each upgrade.  The first one only has one tier.
+    let bread = game.keywords()[1].clone();
+    game.enter_command(&bread);
+    let bought_bread = |events: &[GameEvent]| events.iter()
+        .filter(|e| matches!(e, GameEvent::UpgradePurchased { .. }) && format!("{:?}", e).contains("BurgerBottom"))
+        .count();
+    assert_eq!(bought_bread(&game.take_events()), 1);
+
+    let words: Vec<&str> = general.iter().map(|line| line.split(' ').next().unwrap()).collect();
+    game.enter_command(&words.join(" "));
+    assert_eq!(bought_bread(&game.take_events()), 0);
+}
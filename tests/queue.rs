@@ -0,0 +1,131 @@
+// Customers waiting in line for a free slot in the order bar
+mod common;
+
+use common::{keywords_except, FRAME};
+use res_type_rant::{Game, GameEvent, OuterConfig};
+use serde_json::json;
+
+// Patient customers who only want lettuce, arriving every 'order_period' seconds (before scaling
+// by reputation) into a bar with room for 'visible' orders and a line of up to 'waiting' more
+fn queue_config(order_period: f64, visible: usize, waiting: usize) -> OuterConfig {
+    let mut cfg = common::config_json();
+    cfg["game"]["ingredient_area"]["ingredients"] = json!(["LettuceLeaf"]);
+    cfg["game"]["state"]["day_length"] = json!(1000.0);
+    cfg["game"]["order_bar"]["order_period"] = json!(order_period);
+    cfg["game"]["order_bar"]["max_visible_orders"] = json!(visible);
+    cfg["game"]["order_bar"]["max_waiting"] = json!(waiting);
+
+    let mut order = cfg["ui"]["order_bar"]["orders"][0].clone();
+    order["ings"] = json!([{ "ing": "LettuceLeaf", "chance": 1.0, "price": 10 }]);
+    order["patience_seconds"] = json!(1000.0);
+    order["depreciation_seconds"] = json!(1000.0);
+    cfg["ui"]["order_bar"]["orders"] = json!([order]);
+
+    common::parse_config(cfg)
+}
+
+// Seconds between customers arriving at the start of a game with 'cfg'
+fn arrival_period(cfg: &OuterConfig) -> f64 {
+    cfg.game.order_bar.order_period * cfg.game.reputation.order_period_scale(cfg.game.reputation.starting)
+}
+
+// Step 'game' for the specified number of 'seconds', and return the events it emitted
+fn step_for(game: &mut Game, seconds: f64) -> Vec<GameEvent> {
+    let mut events = Vec::new();
+    for _ in 0..(seconds / FRAME).round() as usize {
+        game.step(FRAME);
+        events.extend(game.take_events());
+    }
+
+    events
+}
+
+// Return the keywords of the orders created in 'events'
+fn created(events: &[GameEvent]) -> Vec<String> {
+    events.iter()
+        .filter_map(|e| match e {
+            GameEvent::OrderCreated { keyword } => Some(keyword.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Deliver lettuce to the order with the specified 'keyword', out of the specified 'orders' on
+// screen.  Return the events up to it being served, and how many seconds that took.
+fn serve(game: &mut Game, keyword: &str, orders: &[String]) -> (Vec<GameEvent>, f64) {
+    let orders: Vec<&str> = orders.iter().map(String::as_str).collect();
+    let lettuce = keywords_except(game, &orders);
+    game.enter_command(&format!("{} {}", lettuce[0], keyword));
+
+    let mut events = Vec::new();
+    for frame in 1..300 {
+        game.step(FRAME);
+        let frame_events = game.take_events();
+        let served = frame_events.iter().any(|e| matches!(e, GameEvent::OrderServed { .. }));
+        events.extend(frame_events);
+        if served {
+            return (events, frame as f64 * FRAME);
+        }
+    }
+
+    panic!("The order wasn't served: {:?}", events);
+}
+
+#[test]
+fn line_fills_up() {
+    let mut cfg = queue_config(1.0, 2, 2);
+    let period = arrival_period(&cfg);
+    cfg.game.state.day_length = 10.5 * period;
+    let mut game = common::new_game(cfg, 1);
+
+    // Two customers get a slot and two more wait in line, while the rest go elsewhere
+    let events = step_for(&mut game, 10.0 * period);
+    assert_eq!(created(&events).len(), 2, "{:?}", events);
+
+    // Everyone who came in misses out when the restaurant closes
+    let before = game.keywords();
+    assert!(step_for(&mut game, period).contains(&GameEvent::DayEnded));
+    let sign = keywords_except(&game, &before.iter().map(String::as_str).collect::<Vec<&str>>());
+    game.enter_command(&sign[0]);
+    let events = step_for(&mut game, 1.0);
+    assert_eq!(events.iter().filter(|e| **e == GameEvent::OrderMissed).count(), 4, "{:?}", events);
+}
+
+#[test]
+fn served_order_promotes_next_in_line() {
+    let cfg = queue_config(5.0, 1, 2);
+    let period = arrival_period(&cfg);
+    let mut game = common::new_game(cfg, 1);
+
+    let first = created(&step_for(&mut game, period + 0.5));
+    assert_eq!(first.len(), 1);
+
+    // The second customer waits in line until the first order is served
+    assert!(created(&step_for(&mut game, period)).is_empty());
+    let (events, _) = serve(&mut game, &first[0], &first);
+    assert_eq!(created(&events).len(), 1, "{:?}", events);
+}
+
+#[test]
+fn promotion_keeps_next_arrival_time() {
+    let cfg = queue_config(10.0, 1, 2);
+    let period = arrival_period(&cfg);
+    let mut game = common::new_game(cfg, 1);
+
+    // The first customer arrives, and the second gets in line
+    let first = created(&step_for(&mut game, period + 0.5));
+    assert!(created(&step_for(&mut game, period + 2.5)).is_empty());
+    let mut elapsed = 2.0 * period + 3.0;
+
+    // Serving the first order promotes the second customer...
+    let (events, seconds) = serve(&mut game, &first[0], &first);
+    let second = created(&events);
+    assert_eq!(second.len(), 1, "{:?}", events);
+    elapsed += seconds;
+
+    // ... but the third still arrives a period after the second did, so is already in line
+    // when the second order is served
+    step_for(&mut game, 3.0 * period + 1.0 - elapsed);
+    let (events, _) = serve(&mut game, &second[0], &second);
+    assert_eq!(created(&events).len(), 1, "The third customer should be waiting: {:?}", events);
+}
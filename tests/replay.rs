@@ -0,0 +1,93 @@
+// Recording a game and playing it back
+mod common;
+
+use common::FRAME;
+use res_type_rant::{Game, GameEvent, Recording};
+
+// Play a game that continues from some saved progress and has its config edited partway through,
+// and return it with the events that happened
+fn play() -> (Game, Vec<GameEvent>) {
+    let mut save_game = common::new_game(common::config(), 5);
+    for _ in 0..300 {
+        save_game.step(FRAME);
+    }
+    let save = save_game.save();
+
+    let mut game = common::new_game(common::config(), 6);
+    game.load(&save).unwrap();
+
+    let mut events = Vec::new();
+    for frame in 0..2000 {
+        game.step(FRAME);
+        if frame % 15 == 0 {
+            let shown = game.keywords();
+            game.enter_command(&shown.join(" "));
+        }
+
+        // Orders come in faster from here on, and the key typed right after is replayed after it
+        if frame == 500 {
+            game.handle_key("a");
+            let mut cfg = game.config().clone();
+            cfg.game.order_bar.order_period /= 2.0;
+            game.change_config(&cfg).unwrap();
+            game.handle_key("Backspace");
+        }
+
+        events.extend(game.take_events());
+    }
+
+    (game, events)
+}
+
+fn replay(recording: &Recording) -> (Game, Vec<GameEvent>) {
+    let mut game = Game::from_recording(recording.clone(), &common::word_lists(), &String::new()).unwrap();
+
+    let mut events = Vec::new();
+    while game.is_replaying() {
+        game.step(FRAME);
+        events.extend(game.take_events());
+    }
+
+    (game, events)
+}
+
+#[test]
+fn replay_matches_recording() {
+    let (game, events) = play();
+    assert!(!game.recording().configs.is_empty());
+
+    let (replayed, replayed_events) = replay(game.recording());
+    assert_eq!(replayed_events, events);
+    assert_eq!(serde_json::to_value(replayed.save()).unwrap(), serde_json::to_value(game.save()).unwrap());
+    assert_eq!(replayed.config().game.order_bar.order_period, game.config().game.order_bar.order_period);
+}
+
+#[test]
+fn replay_survives_json() {
+    let (game, events) = play();
+    let json = serde_json::to_string(game.recording()).unwrap();
+
+    let (_, replayed_events) = replay(&serde_json::from_str(&json).unwrap());
+    assert_eq!(replayed_events, events);
+}
+
+#[test]
+fn replay_with_other_config_rejected() {
+    let (game, _) = play();
+    let seed = game.recording().seed;
+
+    let mut cfg = common::config();
+    cfg.game.state.day_length += 1.0;
+    assert!(common::new_game(cfg, seed).play_recording(game.recording().clone()).is_err());
+
+    assert!(common::new_game(common::config(), seed).play_recording(game.recording().clone()).is_ok());
+}
+
+#[test]
+fn load_after_start_rejected() {
+    let save = common::new_game(common::config(), 1).save();
+
+    let mut game = common::new_game(common::config(), 2);
+    game.step(FRAME);
+    assert!(game.load(&save).is_err());
+}
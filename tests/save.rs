@@ -0,0 +1,49 @@
+// Saving progress and loading it into a new game
+mod common;
+
+use common::FRAME;
+use res_type_rant::{Game, SaveGame};
+
+// Return a game that's been played for a while, so there's progress to save
+fn played_game() -> Game {
+    let mut game = common::new_game(common::config(), 3);
+    for frame in 0..2000 {
+        game.step(FRAME);
+        if frame % 15 == 0 {
+            let shown = game.keywords();
+            game.enter_command(&shown.join(" "));
+        }
+    }
+
+    game
+}
+
+#[test]
+fn round_trip() {
+    let save = played_game().save();
+    let json = serde_json::to_string(&save).unwrap();
+
+    let mut game = common::new_game(common::config(), 4);
+    game.load(&serde_json::from_str::<SaveGame>(&json).unwrap()).unwrap();
+
+    assert_eq!(serde_json::to_value(game.save()).unwrap(), serde_json::to_value(save).unwrap());
+}
+
+#[test]
+fn other_difficulty_rejected() {
+    let save = played_game().save();
+
+    let mut cfg = common::config();
+    cfg.game.difficulty += 1;
+    let mut game = common::new_game(cfg, 4);
+    assert!(game.load(&save).is_err());
+}
+
+#[test]
+fn newer_version_rejected() {
+    let mut save = played_game().save();
+    save.version += 1;
+
+    let mut game = common::new_game(common::config(), 4);
+    assert!(game.load(&save).is_err());
+}
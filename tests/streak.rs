@@ -0,0 +1,119 @@
+// Serve streaks paying bonuses, and what breaks them
+mod common;
+
+use common::{step_until, FRAME};
+use res_type_rant::{Game, GameEvent};
+use serde_json::json;
+
+// Start a game where customers who want $10 of lettuce arrive every second, and each serve in a
+// streak pays $10 more than the last.  Customers leave after 'patience_seconds'.
+fn new_game(patience_seconds: f64) -> Game {
+    let mut cfg = common::config_json();
+    cfg["game"]["ingredient_area"]["ingredients"] = json!(["LettuceLeaf"]);
+    cfg["game"]["order_bar"]["order_period"] = json!(1.0);
+    cfg["game"]["streak"]["multipliers"] = json!([1.0, 2.0, 3.0]);
+
+    let mut order = cfg["ui"]["order_bar"]["orders"][0].clone();
+    order["ings"] = json!([{ "ing": "LettuceLeaf", "chance": 1.0, "price": 10 }]);
+    order["patience_seconds"] = json!(patience_seconds);
+    order["depreciation_seconds"] = json!(1000.0);
+    cfg["ui"]["order_bar"]["orders"] = json!([order]);
+
+    common::new_game(common::parse_config(cfg), 1)
+}
+
+// Wait for the next order, and return its keyword
+fn next_order(game: &mut Game) -> String {
+    match step_until(game, 30.0, |e| matches!(e, GameEvent::OrderCreated { .. })) {
+        Some(GameEvent::OrderCreated { keyword }) => keyword,
+        e => panic!("Expected an order, got {:?}", e),
+    }
+}
+
+// Deliver lettuce to the order with the specified 'keyword', and return the streak bonus paid
+// for it
+fn serve(game: &mut Game, keyword: &str) -> i32 {
+    // The orders' keywords come first, and the lettuce is the only other one on screen
+    let lettuce = game.keywords().pop().unwrap();
+    game.enter_command(&format!("{} {}", lettuce, keyword));
+
+    match step_until(game, 10.0, |e| matches!(e, GameEvent::OrderServed { .. })) {
+        Some(GameEvent::OrderServed { streak_bonus, .. }) => streak_bonus,
+        e => panic!("Expected the order to be served, got {:?}", e),
+    }
+}
+
+#[test]
+fn streak_pays_bonus() {
+    let mut game = new_game(1000.0);
+
+    let bonuses: Vec<i32> = (0..4).map(|_| {
+        let order = next_order(&mut game);
+        serve(&mut game, &order)
+    }).collect();
+
+    assert_eq!(bonuses, vec![0, 10, 20, 20]);
+    assert_eq!(game.stats().today.counters.longest_streak, 4);
+}
+
+#[test]
+fn typo_resets_streak() {
+    let mut game = new_game(1000.0);
+
+    let order = next_order(&mut game);
+    assert_eq!(serve(&mut game, &order), 0);
+    let order = next_order(&mut game);
+    assert_eq!(serve(&mut game, &order), 10);
+
+    // Type a letter that no keyword on screen starts with
+    let shown = game.keywords();
+    let typo = ('a'..='z').find(|c| !shown.iter().any(|kw| kw.starts_with(*c))).unwrap();
+    game.enter_command(&typo.to_string());
+    assert!(game.take_events().contains(&GameEvent::Typo));
+
+    let order = next_order(&mut game);
+    assert_eq!(serve(&mut game, &order), 0);
+}
+
+#[test]
+fn leaving_customer_resets_streak() {
+    let mut game = new_game(4.0);
+
+    let order = next_order(&mut game);
+    assert_eq!(serve(&mut game, &order), 0);
+
+    // Let the customers who came in meanwhile give up, then serve one who just arrived
+    assert!(step_until(&mut game, 30.0, |e| matches!(e, GameEvent::OrderExpired { .. })).is_some());
+    let order = next_order(&mut game);
+    assert_eq!(serve(&mut game, &order), 0);
+}
+
+
+#[test]
+fn missed_order_resets_streak() {
+    let mut game = new_game(1000.0);
+
+    let order = next_order(&mut game);
+    assert_eq!(serve(&mut game, &order), 0);
+
+    // Close with customers still waiting, and open again the next day
+    let mut sign = None;
+    while sign.is_none() {
+        let before = game.keywords();
+        game.step(FRAME);
+        if game.take_events().contains(&GameEvent::DayEnded) {
+            sign = game.keywords().into_iter().find(|kw| !before.contains(kw));
+        }
+    }
+    game.enter_command(&sign.unwrap());
+    assert!(game.take_events().contains(&GameEvent::OrderMissed));
+    let sign = game.keywords().pop().unwrap();
+    game.enter_command(&sign);
+    assert!(game.in_store());
+    let sign = game.keywords()[0].clone();
+    game.enter_command(&sign);
+    assert!(!game.in_store());
+
+    let order = next_order(&mut game);
+    assert_eq!(serve(&mut game, &order), 0);
+}
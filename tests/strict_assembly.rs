@@ -0,0 +1,100 @@
+// Orders that must be assembled bottom to top, at difficulties with strict assembly
+mod common;
+
+use common::{keywords_except, step_until, FRAME};
+use res_type_rant::{Game, GameEvent};
+use serde_json::json;
+
+// Indexes of the ingredients in the ingredient area, which lists them in the order they're configured
+const LETTUCE: usize = 0;
+const TOMATO: usize = 1;
+
+// Start a game with one strict order of lettuce under tomato, and return it with the order's
+// keyword
+fn new_game(strict_assembly: bool) -> (Game, String) {
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(1);
+    cfg["game"]["difficulties"][1]["strict_assembly"] = json!(strict_assembly);
+    cfg["game"]["ingredient_area"]["ingredients"] = json!(["LettuceLeaf", "TomatoSlice"]);
+
+    let mut order = cfg["ui"]["order_bar"]["orders"][0].clone();
+    order["ings"] = json!([
+        { "ing": "LettuceLeaf", "chance": 1.0, "price": 4 },
+        { "ing": "TomatoSlice", "chance": 1.0, "price": 5 }]);
+    order["patience_seconds"] = json!(1000.0);
+    order["strict_order"] = json!(true);
+    cfg["ui"]["order_bar"]["orders"] = json!([order]);
+    let mut game = common::new_game(common::parse_config(cfg), 1);
+
+    match step_until(&mut game, 30.0, |e| matches!(e, GameEvent::OrderCreated { .. })) {
+        Some(GameEvent::OrderCreated { keyword }) => (game, keyword),
+        e => panic!("Expected an order, got {:?}", e),
+    }
+}
+
+// Deliver the ingredient at 'ing_idx' in the ingredient area, which gets a new keyword each time
+// it's used, to the specified 'order'
+fn deliver(game: &mut Game, ing_idx: usize, order: &str) {
+    let ings = keywords_except(game, &[order]);
+    game.enter_command(&format!("{} {}", ings[ing_idx], order));
+}
+
+// Step 'game' for a couple of seconds, and return the events it emitted
+fn settle(game: &mut Game) -> Vec<GameEvent> {
+    let mut events = Vec::new();
+    for _ in 0..60 {
+        game.step(FRAME);
+        events.extend(game.take_events());
+    }
+
+    events
+}
+
+fn served(events: &[GameEvent]) -> bool {
+    events.iter().any(|e| matches!(e, GameEvent::OrderServed { .. }))
+}
+
+#[test]
+fn in_order_delivery_served() {
+    let (mut game, order) = new_game(true);
+
+    deliver(&mut game, LETTUCE, &order);
+    deliver(&mut game, TOMATO, &order);
+    let events = settle(&mut game);
+
+    assert!(served(&events), "{:?}", events);
+    assert!(!events.contains(&GameEvent::DeliveryRejected));
+    assert_eq!(game.stats().today.counters.deliveries_rejected, 0);
+}
+
+#[test]
+fn out_of_order_delivery_rejected() {
+    let (mut game, order) = new_game(true);
+
+    // The tomato can't go on before the lettuce under it
+    deliver(&mut game, TOMATO, &order);
+    let events = settle(&mut game);
+    assert!(events.contains(&GameEvent::DeliveryRejected), "{:?}", events);
+    assert!(!served(&events));
+    assert_eq!(game.stats().today.counters.deliveries_rejected, 1);
+
+    // Starting again from the bottom works
+    deliver(&mut game, LETTUCE, &order);
+    deliver(&mut game, TOMATO, &order);
+    let events = settle(&mut game);
+    assert!(served(&events), "{:?}", events);
+    assert_eq!(game.stats().today.counters.deliveries_rejected, 1);
+}
+
+#[test]
+fn any_order_without_strict_assembly() {
+    let (mut game, order) = new_game(false);
+
+    deliver(&mut game, TOMATO, &order);
+    deliver(&mut game, LETTUCE, &order);
+    let events = settle(&mut game);
+
+    assert!(served(&events), "{:?}", events);
+    assert!(!events.contains(&GameEvent::DeliveryRejected));
+    assert_eq!(game.stats().today.counters.deliveries_rejected, 0);
+}
@@ -0,0 +1,65 @@
+// Picking keywords from the word lists for each difficulty
+mod common;
+
+use res_type_rant::Game;
+use serde_json::json;
+
+fn start(cfg: serde_json::Value) -> Result<Game, String> {
+    Game::new(common::parse_config(cfg), 1, &common::word_lists(), &String::new())
+}
+
+#[test]
+fn every_difficulty_starts() {
+    let difficulties = common::config().game.difficulties.len();
+    for difficulty in 0..difficulties {
+        let mut cfg = common::config_json();
+        cfg["game"]["difficulty"] = json!(difficulty);
+
+        let mut game = start(cfg).unwrap_or_else(|e| panic!("Difficulty {} should start: {}", difficulty, e));
+        for _ in 0..300 {
+            game.step(common::FRAME);
+        }
+        assert!(!game.keywords().is_empty());
+    }
+}
+
+#[test]
+fn empty_level_rejected() {
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(1);
+    cfg["game"]["difficulties"][1]["word_levels"][0]["min_score"] = json!(1000.0);
+    cfg["game"]["difficulties"][1]["word_levels"][0]["max_score"] = json!(1001.0);
+
+    let err = start(cfg).err().expect("A level with no words should be an error");
+    assert!(err.contains("Word level 0"), "{}", err);
+}
+
+#[test]
+fn empty_level_allowed_if_never_picked() {
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(1);
+    cfg["game"]["difficulties"][1]["word_levels"][0]["min_score"] = json!(1000.0);
+    cfg["game"]["difficulties"][1]["word_levels"][0]["max_score"] = json!(1001.0);
+    cfg["game"]["difficulties"][1]["word_levels"][0]["weight"] = json!(0.0);
+
+    assert!(start(cfg).is_ok());
+}
+
+#[test]
+fn untagged_pool_rejected() {
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(1);
+    cfg["game"]["word_pools"]["ingredients"] = json!(["spaceship"]);
+
+    let err = start(cfg).err().expect("A pool with no words should be an error");
+    assert!(err.contains("spaceship"), "{}", err);
+}
+
+#[test]
+fn letter_pairs_ignore_pools() {
+    let mut cfg = common::config_json();
+    cfg["game"]["difficulty"] = json!(0);
+    cfg["game"]["word_pools"]["ingredients"] = json!(["spaceship"]);
+
+    assert!(start(cfg).is_ok());
+}